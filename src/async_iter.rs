@@ -0,0 +1,377 @@
+//! Asynchronous iterators whose steps may fail.
+//!
+//! `AsyncIterrator` is the asynchronous counterpart of `Iterrator`: instead of blocking in `next`
+//! it is polled for its next element. Use `into_stream` and `from_stream` to interoperate with the
+//! `TryStream`s of the `futures` crate.
+//!
+//! This module requires the `async` feature.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::stream::{FusedStream, Stream, TryStream};
+
+/// An asynchronous iterator which may fail to advance.
+pub trait AsyncIterrator{
+    type Item;
+    type Error;
+
+    /// Attempts to advance the iterator.
+    ///
+    /// Returns `Poll::Pending` if the next element is not available yet, in which case the
+    /// current task is woken once the iterator is ready to be polled again. Otherwise the outcome
+    /// matches `Iterrator::next`: `Ok(None)` when iteration is finished, `Err(Error)` if advancing
+    /// failed and `Ok(Some(Item))` otherwise.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>)
+        -> Poll<Result<Option<Self::Item>, Self::Error>>;
+
+    /// Returns the bounds on the remaining length of the iterator.
+    ///
+    /// Each element and each error counts as one element, like for `Iterrator::size_hint`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+
+    /// Returns a future resolving to the next element of the iterator.
+    fn next(&mut self) -> Next<'_, Self> where
+        Self: Unpin
+    {
+        Next{iter: self}
+    }
+
+    /// Takes a closure and creates an iterator which calls that closure on each element.
+    fn map<B, F>(self, f: F) -> Map<Self, F> where
+        Self: Sized, F: FnMut(Self::Item) -> B
+    {
+        Map{iter: self, f}
+    }
+
+    /// Creates an iterator that yields its first `n` elements.
+    ///
+    /// Like `Iterrator::take` an error counts as one of the `n` elements.
+    fn take(self, n: usize) -> Take<Self> where
+        Self: Sized
+    {
+        Take{iter: self, n}
+    }
+
+    /// Returns a future applying a function on all elements, producing a single, final value.
+    ///
+    /// The future resolves to the first error encountered.
+    fn fold<B, F>(self, init: B, f: F) -> Fold<Self, F, B> where
+        Self: Sized, F: FnMut(B, Self::Item) -> B
+    {
+        Fold{iter: self, f, accum: Some(init)}
+    }
+
+    /// Converts the iterator into a `Stream` of `Result`s, which is a `TryStream`.
+    ///
+    /// The stream ends once the iterator returns `Ok(None)`. Errors are yielded as `Err` items.
+    fn into_stream(self) -> IntoStream<Self> where
+        Self: Sized
+    {
+        IntoStream{iter: self, done: false}
+    }
+}
+
+impl<I> AsyncIterrator for &mut I where I: AsyncIterrator + Unpin + ?Sized{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>)
+        -> Poll<Result<Option<I::Item>, I::Error>>
+    {
+        Pin::new(&mut **self).poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+}
+
+/// Converts a `TryStream` into an `AsyncIterrator`.
+///
+/// `Ok` items of the stream become elements of the iterator, `Err` items become errors.
+pub fn from_stream<S>(stream: S) -> FromStream<S> where S: TryStream{
+    FromStream{stream}
+}
+
+/// A future resolving to the next element of an iterator.
+///
+/// This `struct` is created by the `next()` method on `AsyncIterrator`
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Next<'a, I: ?Sized>{
+    iter: &'a mut I,
+}
+
+impl<I> Future for Next<'_, I> where I: AsyncIterrator + Unpin + ?Sized{
+    type Output = Result<Option<I::Item>, I::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.iter).poll_next(cx)
+    }
+}
+
+pin_project!{
+    /// An iterator that maps the elements of `iter` with `f`.
+    ///
+    /// This `struct` is created by the `map()` method on `AsyncIterrator`
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+    #[derive(Clone)]
+    pub struct Map<I, F>{
+        #[pin]
+        iter: I,
+        f: F,
+    }
+}
+
+impl<B, I, F> AsyncIterrator for Map<I, F> where I: AsyncIterrator, F: FnMut(I::Item) -> B{
+    type Item = B;
+    type Error = I::Error;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<Option<B>, I::Error>> {
+        let this = self.project();
+        let f = this.f;
+        this.iter.poll_next(cx).map(|next| next.map(|x| x.map(f)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pin_project!{
+    /// An iterator that only iterates over the first `n` iterations of `iter`.
+    ///
+    /// This `struct` is created by the `take()` method on `AsyncIterrator`
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+    #[derive(Clone)]
+    pub struct Take<I>{
+        #[pin]
+        iter: I,
+        n: usize,
+    }
+}
+
+impl<I> AsyncIterrator for Take<I> where I: AsyncIterrator{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>)
+        -> Poll<Result<Option<I::Item>, I::Error>>
+    {
+        let this = self.project();
+        if *this.n == 0 {
+            return Poll::Ready(Ok(None));
+        }
+        let next = this.iter.poll_next(cx);
+        if next.is_ready() {
+            *this.n -= 1;
+        }
+        next
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let upper = match upper {
+            Some(x) if x < self.n => x,
+            _ => self.n,
+        };
+        (lower.min(self.n), Some(upper))
+    }
+}
+
+pin_project!{
+    /// A future applying a function on all elements of `iter`.
+    ///
+    /// This `struct` is created by the `fold()` method on `AsyncIterrator`
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct Fold<I, F, B>{
+        #[pin]
+        iter: I,
+        f: F,
+        // `None` once the future completed
+        accum: Option<B>,
+    }
+}
+
+impl<I, F, B> Future for Fold<I, F, B> where I: AsyncIterrator, F: FnMut(B, I::Item) -> B{
+    type Output = Result<B, I::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            let next = match this.iter.as_mut().poll_next(cx) {
+                Poll::Ready(next) => next,
+                Poll::Pending => return Poll::Pending,
+            };
+            let accum = this.accum.take().expect("`Fold` polled after completion");
+            match next {
+                Ok(Some(x)) => *this.accum = Some((this.f)(accum, x)),
+                Ok(None) => return Poll::Ready(Ok(accum)),
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+        }
+    }
+}
+
+pin_project!{
+    /// A `Stream` of the elements and errors of `iter`.
+    ///
+    /// This `struct` is created by the `into_stream()` method on `AsyncIterrator`
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Clone)]
+    pub struct IntoStream<I>{
+        #[pin]
+        iter: I,
+        done: bool,
+    }
+}
+
+impl<I> Stream for IntoStream<I> where I: AsyncIterrator{
+    type Item = Result<I::Item, I::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+        let done = this.done;
+        this.iter.poll_next(cx).map(|next| match next {
+            Ok(Some(x)) => Some(Ok(x)),
+            Ok(None) => {
+                *done = true;
+                None
+            }
+            Err(e) => Some(Err(e)),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done { (0, Some(0)) } else { self.iter.size_hint() }
+    }
+}
+
+impl<I> FusedStream for IntoStream<I> where I: AsyncIterrator{
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+pin_project!{
+    /// An iterator over the items of a `TryStream`.
+    ///
+    /// This `struct` is created by the `from_stream()` function
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+    #[derive(Clone)]
+    pub struct FromStream<S>{
+        #[pin]
+        stream: S,
+    }
+}
+
+impl<S> AsyncIterrator for FromStream<S> where S: TryStream{
+    type Item = S::Ok;
+    type Error = S::Error;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>)
+        -> Poll<Result<Option<S::Ok>, S::Error>>
+    {
+        self.project().stream.try_poll_next(cx).map(|next| match next {
+            Some(Ok(x)) => Ok(Some(x)),
+            Some(Err(e)) => Err(e),
+            None => Ok(None),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use futures::executor::block_on;
+    use futures::stream::{self, StreamExt, TryStreamExt};
+    use test_support::Scripted;
+    use Iterrator;
+
+    /// Returns `Poll::Pending` once before each outcome of `iter`
+    struct Delayed{
+        iter: Scripted,
+        ready: bool,
+    }
+
+    impl AsyncIterrator for Delayed{
+        type Item = usize;
+        type Error = &'static str;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>)
+            -> Poll<Result<Option<usize>, &'static str>>
+        {
+            if !self.ready {
+                self.ready = true;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            self.ready = false;
+            Poll::Ready(self.iter.next())
+        }
+    }
+
+    fn delayed(script: Vec<Result<Option<usize>, &'static str>>) -> Delayed {
+        Delayed{iter: Scripted::new(script), ready: false}
+    }
+
+    #[test]
+    fn next() {
+
+        let mut it = delayed(vec![Ok(Some(1)), Err("fail"), Ok(Some(2))]);
+        assert_eq!(block_on(it.next()), Ok(Some(1)));
+        assert_eq!(block_on(it.next()), Err("fail"));
+        assert_eq!(block_on(it.next()), Ok(Some(2)));
+        assert_eq!(block_on(it.next()), Ok(None));
+    }
+
+    #[test]
+    fn map_take_fold() {
+
+        let it = delayed(vec![Ok(Some(1)), Ok(Some(2)), Ok(Some(3)), Ok(Some(4))]);
+        let sum = it.map(|n| n * 10).take(3).fold(0, |a, b| a + b);
+        assert_eq!(block_on(sum), Ok(60));
+        let fail = delayed(vec![Ok(Some(1)), Err("fail"), Ok(Some(2))]).fold(0, |a, b| a + b);
+        assert_eq!(block_on(fail), Err("fail"));
+    }
+
+    #[test]
+    fn take_counts_errors() {
+
+        let it = delayed(vec![Err("fail"), Ok(Some(1)), Ok(Some(2))]).take(2);
+        let items: Vec<_> = block_on(it.into_stream().collect());
+        assert_eq!(items, vec![Err("fail"), Ok(1)]);
+    }
+
+    #[test]
+    fn into_stream() {
+
+        let it = delayed(vec![Ok(Some(1)), Ok(Some(2))]);
+        assert_eq!(block_on(it.into_stream().try_collect::<Vec<_>>()), Ok(vec![1, 2]));
+        let it = delayed(vec![Ok(Some(1)), Err("fail")]);
+        assert_eq!(block_on(it.into_stream().try_collect::<Vec<_>>()), Err("fail"));
+    }
+
+    #[test]
+    fn from_stream() {
+
+        let s = stream::iter(vec![Ok(1), Ok(2), Err("fail"), Ok(3)]);
+        let mut it = super::from_stream(s).map(|n| n + 1);
+        assert_eq!(block_on(it.next()), Ok(Some(2)));
+        assert_eq!(block_on(it.next()), Ok(Some(3)));
+        assert_eq!(block_on(it.next()), Err("fail"));
+        assert_eq!(block_on(it.next()), Ok(Some(4)));
+        assert_eq!(block_on(it.next()), Ok(None));
+    }
+}
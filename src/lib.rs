@@ -1,5 +1,37 @@
 //! A crate containing traits and functions for iterators those iterator steps may fail
 
+#[cfg(feature = "async")]
+extern crate futures_core;
+#[cfg(feature = "async")]
+#[macro_use]
+extern crate pin_project_lite;
+#[cfg(all(test, feature = "async"))]
+extern crate futures;
+
+use std::borrow::Cow;
+use std::cmp;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::convert::Infallible;
+use std::hash::{BuildHasher, Hash};
+use std::iter::{FromIterator, FusedIterator, Product, Sum};
+use std::marker::PhantomData;
+use std::ops::ControlFlow;
+
+#[cfg(feature = "parallel")]
+use parallel::ParMap;
+use prefetch::Prefetch;
+use retry::{Retry, RetryPolicy, Sleeper, ThreadSleeper};
+
+#[cfg(feature = "async")]
+pub mod async_iter;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod prefetch;
+pub mod retry;
+pub mod streaming;
+#[cfg(test)]
+pub(crate) mod test_support;
+
 /// An iterator that only iterates over the first `n` iterations of `iter`.
 ///
 /// This `struct` is created by the `take()` method on `Iterrator`
@@ -10,6 +42,120 @@ pub struct Take<I>{
     n: usize,
 }
 
+/// An iterator that yields the current count and the element during iteration.
+///
+/// Only elements are counted, errors are not. For the same reason `Enumerate` is not a
+/// `DoubleEndedIterrator`: the index of the last element depends on the number of errors before
+/// it, which is not known without advancing from the front.
+///
+/// This `struct` is created by the `enumerate()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Enumerate<I>{
+    iter: I,
+    count: usize,
+}
+
+/// An iterator that skips over the first `n` elements of `iter`.
+///
+/// This `struct` is created by the `skip()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Skip<I>{
+    iter: I,
+    n: usize,
+}
+
+/// An iterator for stepping iterators by a custom amount.
+///
+/// This `struct` is created by the `step_by()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct StepBy<I>{
+    iter: I,
+    // Number of elements to skip between two yielded ones
+    step: usize,
+    // Number of elements left to skip before the next yielded one
+    skip: usize,
+}
+
+/// An iterator that rejects elements while `predicate` returns `true`.
+///
+/// This `struct` is created by the `skip_while()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct SkipWhile<I,P>{
+    iter: I,
+    flag: bool,
+    predicate: P,
+}
+
+/// An iterator that only accepts elements while `predicate` returns `true`.
+///
+/// This `struct` is created by the `take_while()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct TakeWhile<I,P>{
+    iter: I,
+    flag: bool,
+    predicate: P,
+}
+
+/// An iterator that only accepts elements while `predicate` returns `Some(_)`.
+///
+/// This `struct` is created by the `map_while()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct MapWhile<I,P>{
+    iter: I,
+    predicate: P,
+}
+
+/// An iterator that rejects elements while a fallible `predicate` returns `Ok(true)`.
+///
+/// This `struct` is created by the `try_skip_while()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct TrySkipWhile<I,P>{
+    iter: I,
+    flag: bool,
+    predicate: P,
+}
+
+/// An iterator that only accepts elements while a fallible `predicate` returns `Ok(true)`.
+///
+/// This `struct` is created by the `try_take_while()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct TryTakeWhile<I,P>{
+    iter: I,
+    flag: bool,
+    predicate: P,
+}
+
+/// An iterator to maintain state while iterating another iterator.
+///
+/// This `struct` is created by the `scan()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Scan<I,St,F>{
+    iter: I,
+    f: F,
+    state: St,
+}
+
+/// An iterator to maintain state while iterating another iterator, using a closure which may
+/// fail.
+///
+/// This `struct` is created by the `try_scan()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct TryScan<I,St,F>{
+    iter: I,
+    f: F,
+    state: St,
+}
+
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
 #[derive(Clone)]
 pub struct Map<I,F>{
@@ -17,6 +163,298 @@ pub struct Map<I,F>{
     f: F,
 }
 
+/// An iterator that maps its elements using a closure which may fail.
+///
+/// This `struct` is created by the `and_then()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct AndThen<I,F>{
+    iter: I,
+    f: F,
+}
+
+/// An iterator that maps its elements using a closure which may fail with an error convertible
+/// into the error type of the underlying iterator.
+///
+/// This `struct` is created by the `try_map()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct TryMap<I,F>{
+    iter: I,
+    f: F,
+}
+
+/// An iterator that maps the errors of `iter` using a closure.
+///
+/// This `struct` is created by the `map_err()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct MapErr<I,F>{
+    iter: I,
+    f: F,
+}
+
+/// An iterator that converts the errors of `iter` into `E` using `From`.
+///
+/// This `struct` is created by the `err_into()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct ErrInto<I,E>{
+    iter: I,
+    _error: PhantomData<fn() -> E>,
+}
+
+impl<I, E> Clone for ErrInto<I, E> where I: Clone{
+    fn clone(&self) -> Self {
+        ErrInto{iter: self.iter.clone(), _error: PhantomData}
+    }
+}
+
+/// An iterator that calls a function with a reference to each element before yielding it.
+///
+/// This `struct` is created by the `inspect()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Inspect<I,F>{
+    iter: I,
+    f: F,
+}
+
+/// An iterator that calls a function with a reference to each error before returning it.
+///
+/// This `struct` is created by the `inspect_err()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct InspectErr<I,F>{
+    iter: I,
+    f: F,
+}
+
+/// An iterator that skips the errors of `iter`.
+///
+/// This `struct` is created by the `skip_errors()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct SkipErrors<I>{
+    iter: I,
+}
+
+/// An iterator that replaces or skips errors of `iter` using a closure.
+///
+/// This `struct` is created by the `recover()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Recover<I,F>{
+    iter: I,
+    f: F,
+}
+
+/// An iterator that handles each error of `iter` with a closure, which may replace it with an
+/// element or another error.
+///
+/// This `struct` is created by the `or_else()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct OrElse<I,F>{
+    iter: I,
+    f: F,
+}
+
+/// An `Iterrator` yielding the `Ok` values of a `std::iter::Iterator` over `Result`s and failing
+/// with its `Err` values.
+///
+/// This `struct` is created by the `convert()` function
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Convert<I>{
+    iter: I,
+}
+
+/// A `std::iter::Iterator` yielding the results of advancing an `Iterrator`.
+///
+/// This `struct` is created by the `iterator()` method on `Iterrator`
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Iter<I>{
+    iter: I,
+}
+
+/// An `Iterrator` yielding the elements of a `std::iter::Iterator`. It never fails.
+///
+/// This `struct` is created by the `from_iter()` function
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct FromIter<I, E = Infallible>{
+    iter: I,
+    _error: PhantomData<fn() -> E>,
+}
+
+impl<I, E> Clone for FromIter<I, E> where I: Clone{
+    fn clone(&self) -> Self {
+        FromIter{iter: self.iter.clone(), _error: PhantomData}
+    }
+}
+
+/// A `std::iter::Iterator` yielding the elements of an `Iterrator` which can not fail.
+///
+/// This `struct` is created by the `unwrap_infallible()` method on `Iterrator`
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct UnwrapInfallible<I>{
+    iter: I,
+}
+
+/// A double-ended iterator with the direction inverted.
+///
+/// This `struct` is created by the `rev()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Rev<I>{
+    iter: I,
+}
+
+/// An iterator that filters the elements of `iter` with `predicate`.
+///
+/// This `struct` is created by the `filter()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Filter<I,P>{
+    iter: I,
+    predicate: P,
+}
+
+/// An iterator that uses `f` to both filter and map elements from `iter`.
+///
+/// This `struct` is created by the `filter_map()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct FilterMap<I,F>{
+    iter: I,
+    f: F,
+}
+
+/// An iterator that filters the elements of `iter` with a `predicate` which may fail.
+///
+/// This `struct` is created by the `try_filter()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct TryFilter<I,P>{
+    iter: I,
+    predicate: P,
+}
+
+/// An iterator with a `peek()` that returns an optional reference to the next element.
+///
+/// Peeking advances the underlying iterator and buffers the outcome. This holds for errors, too:
+/// A peeked error is returned again by the next call to `peek` and by the next call to `next`,
+/// which removes it from the buffer.
+///
+/// This `struct` is created by the `peekable()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Peekable<I> where I: Iterrator{
+    iter: I,
+    peeked: Option<Result<Option<I::Item>, I::Error>>,
+}
+
+/// An iterator that yields `Ok(None)` forever after the underlying iterator finished or failed.
+///
+/// This `struct` is created by the `fuse()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Fuse<I>{
+    iter: Option<I>,
+}
+
+/// An iterator that links two iterators together, in a chain.
+///
+/// This `struct` is created by the `chain()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Chain<A,B>{
+    // Set to `None` once exhausted
+    a: Option<A>,
+    b: Option<B>,
+}
+
+/// An iterator that maps each element to an `Iterrator`, and yields the elements of the produced
+/// iterators.
+///
+/// This `struct` is created by the `flat_map()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct FlatMap<I,U,F>{
+    iter: I,
+    f: F,
+    front: Option<U>,
+}
+
+/// An iterator that flattens one level of nesting in an iterator of `Iterrator`s.
+///
+/// This `struct` is created by the `flatten()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Flatten<I> where I: Iterrator, I::Item: Iterrator{
+    iter: I,
+    front: Option<I::Item>,
+}
+
+/// An iterator that maps each element to a `std::iter::IntoIterator`, and yields the elements of
+/// the produced iterators.
+///
+/// This `struct` is created by the `flat_map_iter()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct FlatMapIter<I,U,F> where U: IntoIterator{
+    iter: I,
+    f: F,
+    front: Option<U::IntoIter>,
+}
+
+impl<I, U, F> Clone for FlatMapIter<I,U,F> where
+    I: Clone, F: Clone, U: IntoIterator, U::IntoIter: Clone
+{
+    fn clone(&self) -> Self {
+        FlatMapIter{iter: self.iter.clone(), f: self.f.clone(), front: self.front.clone()}
+    }
+}
+
+/// An iterator that flattens one level of nesting in an iterator of `std::iter::IntoIterator`s.
+///
+/// This `struct` is created by the `flatten_iter()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct FlattenIter<I> where I: Iterrator, I::Item: IntoIterator{
+    iter: I,
+    front: Option<<I::Item as IntoIterator>::IntoIter>,
+}
+
+impl<I> Clone for FlattenIter<I> where
+    I: Iterrator + Clone,
+    I::Item: IntoIterator,
+    <I::Item as IntoIterator>::IntoIter: Clone
+{
+    fn clone(&self) -> Self {
+        FlattenIter{iter: self.iter.clone(), front: self.front.clone()}
+    }
+}
+
+/// An iterator that iterates two other iterators simultaneously.
+///
+/// This `struct` is created by the `zip()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Zip<A,B>{
+    a: A,
+    b: B,
+}
+
+/// An iterator that links any number of iterators together, in a chain.
+///
+/// This `struct` is created by the `chain_all()` function
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct ChainAll<I> where I: Iterator, I::Item: Iterrator{
+    iters: I,
+    current: Option<I::Item>,
+}
+
 /// An iterator which may or may not succeed to advance to its next element
 pub trait Iterrator{
     type Item;
@@ -26,8 +464,21 @@ pub trait Iterrator{
     ///
     /// If advancing fails it will return `Err(Error)`. Returns `Ok(None)` when iteration is
     /// finished, otherwise `Ok(Some(Item))` is returned.
+    ///
+    /// Once `Ok(None)` or an error has been returned, the outcome of further calls is up to the
+    /// implementation, unless the iterator implements `FusedIterrator` or, in case of an error,
+    /// `ResumableIterrator`. Use `fuse` to obtain an iterator which is guaranteed to keep
+    /// returning `Ok(None)`.
     fn next(&mut self) -> Result<Option<Self::Item>, Self::Error>;
 
+    /// Returns the bounds on the remaining length of the iterator.
+    ///
+    /// Each call to `next` returning either `Ok(Some(Item))` or `Err(Error)` counts as one
+    /// element. The default implementation returns `(0, None)` which is correct for any iterator.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+
     /// An iterator adaptor that applies a function, producing a single, final value.
     fn fold<B, F>(mut self, init:B, mut f: F) -> Result<B, Self::Error> where
         Self: Sized, F: FnMut(B, Self::Item) -> B
@@ -39,6 +490,25 @@ pub trait Iterrator{
         Ok(accum)
     }
 
+    /// An iterator method that applies a function as long as it returns successfully, producing
+    /// a single, final value.
+    ///
+    /// The closure returns a `Try` value like `Result` or `ControlFlow`. Folding stops at the first
+    /// error of the iterator, which is returned as `Err`, or as soon as the closure breaks, in which
+    /// case the closure's value is returned as `Ok`.
+    fn try_fold<B, F, R>(&mut self, init: B, mut f: F) -> Result<R, Self::Error> where
+        Self: Sized, F: FnMut(B, Self::Item) -> R, R: Try<Output = B>
+    {
+        let mut accum = init;
+        while let Some(x) = self.next()?{
+            match f(accum, x).branch() {
+                ControlFlow::Continue(c) => accum = c,
+                ControlFlow::Break(residual) => return Ok(R::from_residual(residual)),
+            }
+        }
+        Ok(R::from_output(accum))
+    }
+
     /// Takes a closure and creates an iterator which calls that closure on each element.
     fn map<F>(self, f: F) -> Map<Self, F> where
         Self: Sized
@@ -47,77 +517,3155 @@ pub trait Iterrator{
     }
 
     /// Creates an iterator that yields its first n elements.
+    ///
+    /// An error counts as one of the n elements. When iterating from the back, errors of the
+    /// elements past the first n are dropped along with the elements, just like iterating from
+    /// the front never reaches them.
     fn take(self, n: usize) -> Take<Self> where
         Self: Sized
     {
         Take{iter: self, n}
     }
-}
 
-impl<I> Iterrator for Take<I> where I: Iterrator{
-    type Item = I::Item;
-    type Error = I::Error;
+    /// Creates an iterator which gives the current iteration count as well as the next value.
+    fn enumerate(self) -> Enumerate<Self> where
+        Self: Sized
+    {
+        Enumerate{iter: self, count: 0}
+    }
+
+    /// Creates an iterator that skips the first `n` elements.
+    ///
+    /// An error counts as one of the skipped elements. It is returned nevertheless, and the next
+    /// call to `next` continues skipping the remaining elements. Since the number of errors
+    /// among the skipped elements is not known in advance, `Skip` never knows its exact length.
+    ///
+    /// Iterating from the back stops before the skipped elements without reaching them, so unlike
+    /// iterating from the front, it never returns their errors.
+    fn skip(self, n: usize) -> Skip<Self> where
+        Self: Sized
+    {
+        Skip{iter: self, n}
+    }
+
+    /// Creates an iterator starting at the same point, but stepping by the given amount at each
+    /// iteration. The first element is always yielded.
+    ///
+    /// Like with `skip`, an error counts as one of the elements stepped over and is returned
+    /// nevertheless, so the errors keep the stride intact.
+    ///
+    /// # Panics
+    ///
+    /// The method will panic if the given step is `0`.
+    fn step_by(self, step: usize) -> StepBy<Self> where
+        Self: Sized
+    {
+        assert!(step != 0);
+        StepBy{iter: self, step: step - 1, skip: 0}
+    }
+
+    /// Creates an iterator that skips elements based on a predicate.
+    fn skip_while<P>(self, predicate: P) -> SkipWhile<Self, P> where
+        Self: Sized, P: FnMut(&Self::Item) -> bool
+    {
+        SkipWhile{iter: self, flag: false, predicate}
+    }
+
+    /// Creates an iterator that yields elements based on a predicate. The first element not
+    /// matching the predicate is consumed and ends the iteration.
+    fn take_while<P>(self, predicate: P) -> TakeWhile<Self, P> where
+        Self: Sized, P: FnMut(&Self::Item) -> bool
+    {
+        TakeWhile{iter: self, flag: false, predicate}
+    }
+
+    /// Creates an iterator that both yields elements based on a predicate and maps.
+    fn map_while<B, P>(self, predicate: P) -> MapWhile<Self, P> where
+        Self: Sized, P: FnMut(Self::Item) -> Option<B>
+    {
+        MapWhile{iter: self, predicate}
+    }
+
+    /// Like `skip_while`, but the predicate may fail. Its error is returned by `next` just like an
+    /// error of the underlying iterator would be.
+    fn try_skip_while<P>(self, predicate: P) -> TrySkipWhile<Self, P> where
+        Self: Sized, P: FnMut(&Self::Item) -> Result<bool, Self::Error>
+    {
+        TrySkipWhile{iter: self, flag: false, predicate}
+    }
+
+    /// Like `take_while`, but the predicate may fail. Its error is returned by `next` just like an
+    /// error of the underlying iterator would be.
+    fn try_take_while<P>(self, predicate: P) -> TryTakeWhile<Self, P> where
+        Self: Sized, P: FnMut(&Self::Item) -> Result<bool, Self::Error>
+    {
+        TryTakeWhile{iter: self, flag: false, predicate}
+    }
+
+    /// An iterator adaptor which, like `fold`, holds internal state, but unlike `fold`, produces
+    /// a new iterator.
+    ///
+    /// The closure is passed a mutable reference to the state and an element. It may return
+    /// `None` to end the iteration.
+    fn scan<St, B, F>(self, initial_state: St, f: F) -> Scan<Self, St, F> where
+        Self: Sized, F: FnMut(&mut St, Self::Item) -> Option<B>
+    {
+        Scan{iter: self, f, state: initial_state}
+    }
+
+    /// Like `scan`, but the closure may fail. Its error is returned by `next` just like an error
+    /// of the underlying iterator would be.
+    fn try_scan<St, B, F>(self, initial_state: St, f: F) -> TryScan<Self, St, F> where
+        Self: Sized, F: FnMut(&mut St, Self::Item) -> Result<Option<B>, Self::Error>
+    {
+        TryScan{iter: self, f, state: initial_state}
+    }
+
+    /// Takes a fallible closure and creates an iterator which calls that closure on each element.
+    ///
+    /// If the closure fails, its error is returned by `next` just like an error of the underlying
+    /// iterator would be.
+    fn and_then<B, F>(self, f: F) -> AndThen<Self, F> where
+        Self: Sized, F: FnMut(Self::Item) -> Result<B, Self::Error>
+    {
+        AndThen{iter: self, f}
+    }
+
+    /// Like `and_then`, but the error returned by the closure is converted into `Self::Error`
+    /// using `From`.
+    fn try_map<B, E, F>(self, f: F) -> TryMap<Self, F> where
+        Self: Sized, F: FnMut(Self::Item) -> Result<B, E>, Self::Error: From<E>
+    {
+        TryMap{iter: self, f}
+    }
+
+    /// Takes a closure and creates an iterator which calls that closure on each error.
+    fn map_err<E, F>(self, f: F) -> MapErr<Self, F> where
+        Self: Sized, F: FnMut(Self::Error) -> E
+    {
+        MapErr{iter: self, f}
+    }
+
+    /// Creates an iterator which converts each error into `E` using `From`.
+    fn err_into<E>(self) -> ErrInto<Self, E> where
+        Self: Sized, E: From<Self::Error>
+    {
+        ErrInto{iter: self, _error: PhantomData}
+    }
+
+    /// Does something with each element of an iterator, passing the value on.
+    fn inspect<F>(self, f: F) -> Inspect<Self, F> where
+        Self: Sized, F: FnMut(&Self::Item)
+    {
+        Inspect{iter: self, f}
+    }
+
+    /// Does something with each error of an iterator, passing the error on.
+    fn inspect_err<F>(self, f: F) -> InspectErr<Self, F> where
+        Self: Sized, F: FnMut(&Self::Error)
+    {
+        InspectErr{iter: self, f}
+    }
+
+    /// Creates an iterator which skips errors and only yields the elements of `self`.
+    ///
+    /// The returned iterator never fails. Only available for a `ResumableIterrator`, since the
+    /// iterator is polled again after each error. Be aware that a source which fails forever
+    /// makes `next` loop forever.
+    fn skip_errors(self) -> SkipErrors<Self> where
+        Self: Sized + ResumableIterrator
+    {
+        SkipErrors{iter: self}
+    }
+
+    /// Creates an iterator which calls a closure on each error to recover from it.
+    ///
+    /// If the closure returns `Ok(Some(Item))`, the element takes the place of the error. If it
+    /// returns `Ok(None)` the error is skipped and the iterator is polled again. An error
+    /// returned by the closure is returned by `next`. Only available for a `ResumableIterrator`,
+    /// since the iterator is polled again after each error.
+    fn recover<F>(self, f: F) -> Recover<Self, F> where
+        Self: Sized + ResumableIterrator,
+        F: FnMut(Self::Error) -> Result<Option<Self::Item>, Self::Error>
+    {
+        Recover{iter: self, f}
+    }
+
+    /// Creates an iterator which calls a closure on each error, replacing it with either an
+    /// element or another error.
+    ///
+    /// Unlike `recover` this is available for any iterator, since it does not poll `self` on its
+    /// own. Whether iteration goes on after a replaced error depends on `self`: A
+    /// `FusedIterrator` ends, a `ResumableIterrator` continues with its next element.
+    fn or_else<E, F>(self, f: F) -> OrElse<Self, F> where
+        Self: Sized, F: FnMut(Self::Error) -> Result<Self::Item, E>
+    {
+        OrElse{iter: self, f}
+    }
+
+    /// Creates a `std::iter::Iterator` yielding `Ok(Item)` for each element and `Err(Error)` for
+    /// each failure, so `Iterrator`s can be used in `for` loops and with std combinators.
+    fn iterator(self) -> Iter<Self> where
+        Self: Sized
+    {
+        Iter{iter: self}
+    }
+
+    /// Creates a `std::iter::Iterator` yielding the elements of an `Iterrator` which can not fail.
+    fn unwrap_infallible(self) -> UnwrapInfallible<Self> where
+        Self: Sized + Iterrator<Error = Infallible>
+    {
+        UnwrapInfallible{iter: self}
+    }
+
+    /// Creates an iterator which uses a closure to determine if an element should be yielded.
+    fn filter<P>(self, predicate: P) -> Filter<Self, P> where
+        Self: Sized, P: FnMut(&Self::Item) -> bool
+    {
+        Filter{iter: self, predicate}
+    }
+
+    /// Creates an iterator that both filters and maps.
+    fn filter_map<B, F>(self, f: F) -> FilterMap<Self, F> where
+        Self: Sized, F: FnMut(Self::Item) -> Option<B>
+    {
+        FilterMap{iter: self, f}
+    }
+
+    /// Like `filter`, but the predicate may fail. Its error is returned by `next` just like an
+    /// error of the underlying iterator would be.
+    fn try_filter<P>(self, predicate: P) -> TryFilter<Self, P> where
+        Self: Sized, P: FnMut(&Self::Item) -> Result<bool, Self::Error>
+    {
+        TryFilter{iter: self, predicate}
+    }
+
+    /// Takes two iterators and creates a new iterator over both in sequence.
+    ///
+    /// An error of the first iterator does not end it, so the next call to `next` polls the first
+    /// iterator again. Only once it returned `Ok(None)` the second iterator is polled. Hence the
+    /// chain is not a `FusedIterrator` even if both iterators are: a fused first iterator returns
+    /// `Ok(None)` after its error, and the chain moves on to the second one.
+    fn chain<U>(self, other: U) -> Chain<Self, U> where
+        Self: Sized, U: Iterrator<Item = Self::Item, Error = Self::Error>
+    {
+        Chain{a: Some(self), b: Some(other)}
+    }
+
+    /// Creates an iterator that works like map, but flattens the `Iterrator`s returned by `f`.
+    ///
+    /// Errors of the inner iterators are converted into `Self::Error` using `From`. Neither an
+    /// error of `self` nor one of an inner iterator ends iteration, so the next call to `next`
+    /// polls the iterator which failed again.
+    fn flat_map<U, F>(self, f: F) -> FlatMap<Self, U, F> where
+        Self: Sized, U: Iterrator, Self::Error: From<U::Error>, F: FnMut(Self::Item) -> U
+    {
+        FlatMap{iter: self, f, front: None}
+    }
+
+    /// Creates an iterator that flattens nested `Iterrator`s.
+    ///
+    /// Errors are handled just like they are by `flat_map`.
+    fn flatten(self) -> Flatten<Self> where
+        Self: Sized, Self::Item: Iterrator,
+        Self::Error: From<<Self::Item as Iterrator>::Error>
+    {
+        Flatten{iter: self, front: None}
+    }
+
+    /// Like `flat_map`, but `f` returns an infallible `std::iter::IntoIterator`.
+    fn flat_map_iter<U, F>(self, f: F) -> FlatMapIter<Self, U, F> where
+        Self: Sized, U: IntoIterator, F: FnMut(Self::Item) -> U
+    {
+        FlatMapIter{iter: self, f, front: None}
+    }
+
+    /// Like `flatten`, but for iterators over infallible `std::iter::IntoIterator`s.
+    fn flatten_iter(self) -> FlattenIter<Self> where
+        Self: Sized, Self::Item: IntoIterator
+    {
+        FlattenIter{iter: self, front: None}
+    }
+
+    /// 'Zips up' two iterators into a single iterator of pairs.
+    ///
+    /// Each call to `next` advances `self` first. If `self` fails or is exhausted, its outcome is
+    /// returned and `other` is not polled. Otherwise `other` is advanced, and if it fails or is
+    /// exhausted, the element taken from `self` is dropped and the outcome of `other` is
+    /// returned. So if both iterators would fail, the error of `self` wins.
+    ///
+    /// Since the errors of `self` do not advance `other`, the length of `other` does not limit
+    /// the number of outcomes, and `Zip` never knows its exact length.
+    fn zip<U>(self, other: U) -> Zip<Self, U> where
+        Self: Sized, U: Iterrator<Error = Self::Error>
+    {
+        Zip{a: self, b: other}
+    }
+
+    /// Creates an iterator which calls `next` again after it failed, as long as `policy` permits
+    /// it. Waits for the delays chosen by the policy by blocking the current thread.
+    ///
+    /// Retrying is meant for transient errors, after which `next` may be called again to retry
+    /// the failed step. If `self` returns `Ok(None)` instead, e.g. because it is fused, the error
+    /// is returned rather than ending iteration.
+    ///
+    /// # Warning
+    ///
+    /// Do not retry a `ResumableIterrator`. It moves on to its next element after an error, so the
+    /// failed element is skipped without the error ever being returned. Use `skip_errors`,
+    /// `recover` or `collect_partitioned` to deal with the errors of such an iterator instead.
+    fn retry<P>(self, policy: P) -> Retry<Self, P> where
+        Self: Sized, P: RetryPolicy<Self::Error>
+    {
+        Retry::new(self, policy, ThreadSleeper)
+    }
+
+    /// Like `retry`, but waits for the delays chosen by the policy using `sleeper`.
+    ///
+    /// The warning on `retry` about `ResumableIterrator`s applies as well.
+    fn retry_with_sleeper<P, S>(self, policy: P, sleeper: S) -> Retry<Self, P, S> where
+        Self: Sized, P: RetryPolicy<Self::Error>, S: Sleeper
+    {
+        Retry::new(self, policy, sleeper)
+    }
+
+    /// Takes a fallible closure and creates an iterator which calls that closure on each element,
+    /// using `threads` worker threads.
+    ///
+    /// The elements are pulled from `self` on the calling thread, at most `2 * threads` ahead of
+    /// the element returned last. Results are returned in the order of the elements. Errors, of
+    /// `self` or of the closure, are returned at the position of their element. Iteration ends
+    /// after the first error, elements the workers have not started on are skipped. If the closure
+    /// panics, the panic is propagated to the caller of `next`.
+    ///
+    /// Requires the `parallel` feature.
+    ///
+    /// # Panics
+    ///
+    /// The method will panic if `threads` is `0`.
+    #[cfg(feature = "parallel")]
+    fn par_map<B, F>(self, threads: usize, f: F) -> ParMap<Self, B> where
+        Self: Sized,
+        Self::Item: Send + 'static,
+        Self::Error: Send + 'static,
+        B: Send + 'static,
+        F: Fn(Self::Item) -> Result<B, Self::Error> + Send + Sync + 'static
+    {
+        ParMap::new(self, threads, f)
+    }
+
+    /// Creates an iterator which calls `next` of `self` on a background thread, reading up to `n`
+    /// elements ahead.
+    ///
+    /// The background thread stops after `Ok(None)` or the first error, which are returned by
+    /// `next` once the elements before them have been consumed. Dropping the iterator stops the
+    /// background thread and waits for its current call to `next` to return. If `self` panics, the
+    /// panic is propagated to the caller of `next`.
+    fn prefetch(self, n: usize) -> Prefetch<Self::Item, Self::Error> where
+        Self: Sized + Send + 'static, Self::Item: Send + 'static, Self::Error: Send + 'static
+    {
+        Prefetch::new(self, n)
+    }
+
+    /// Creates an iterator which ends after the first `Ok(None)` or the first error.
+    ///
+    /// The first error is still returned, but every call to `next` after it returns `Ok(None)`
+    /// without polling the underlying iterator again.
+    fn fuse(self) -> Fuse<Self> where
+        Self: Sized
+    {
+        Fuse{iter: Some(self)}
+    }
+
+    /// Creates an iterator which can use `peek` to look at the next element without consuming it.
+    fn peekable(self) -> Peekable<Self> where
+        Self: Sized
+    {
+        Peekable{iter: self, peeked: None}
+    }
+
+    /// Reverses an iterator's direction.
+    fn rev(self) -> Rev<Self> where
+        Self: Sized + DoubleEndedIterrator
+    {
+        Rev{iter: self}
+    }
+
+    /// Transforms an iterator into a collection.
+    ///
+    /// Iteration stops at the first error, which is then returned instead of the collection. Use
+    /// `collect_into_std` for collections which only implement `std::iter::FromIterator`.
+    fn collect<C>(self) -> Result<C, Self::Error> where
+        Self: Sized, C: FromIterrator<Self::Item>
+    {
+        C::from_iterrator(self)
+    }
+
+    /// Transforms an iterator into any collection implementing `std::iter::FromIterator`.
+    ///
+    /// Works like `collect`, but for collections which do not implement `FromIterrator`, such as
+    /// `Rc<[T]>`, `Box<str>` or collections of other crates. Iteration stops at the first error,
+    /// which is then returned instead of the collection.
+    fn collect_into_std<C>(self) -> Result<C, Self::Error> where
+        Self: Sized, C: FromIterator<Self::Item>
+    {
+        shunt(self, |it| it.collect())
+    }
+
+    /// Consumes the iterator, collecting all elements as well as all errors.
+    ///
+    /// Unlike `collect` this does not stop at the first error, which is why it is only available
+    /// for a `ResumableIterrator`.
+    fn collect_partitioned(mut self) -> (Vec<Self::Item>, Vec<Self::Error>) where
+        Self: Sized + ResumableIterrator
+    {
+        let mut items = Vec::with_capacity(self.size_hint().0);
+        let mut errors = Vec::new();
+        loop {
+            match self.next() {
+                Ok(Some(x)) => items.push(x),
+                Ok(None) => break,
+                Err(e) => errors.push(e),
+            }
+        }
+        (items, errors)
+    }
+
+    /// Transforms an iterator into a collection, or returns every error encountered.
+    ///
+    /// Unlike `collect` this does not stop at the first error, which is why it is only available
+    /// for a `ResumableIterrator`. The collection is only returned if no error occurred.
+    fn collect_all_errors<C>(self) -> Result<C, Vec<Self::Error>> where
+        Self: Sized + ResumableIterrator, C: FromIterrator<Self::Item>
+    {
+        let mut errors = Vec::new();
+        let collection = C::from_iterrator(self.recover(|e| {
+            errors.push(e);
+            Ok(None)
+        }));
+        match collection {
+            Ok(collection) => if errors.is_empty() {
+                Ok(collection)
+            } else {
+                Err(errors)
+            },
+            Err(e) => {
+                errors.push(e);
+                Err(errors)
+            }
+        }
+    }
+
+    /// Borrows an iterator, rather than consuming it.
+    ///
+    /// This is useful to apply adaptors while still retaining ownership of the original iterator.
+    fn by_ref(&mut self) -> &mut Self where
+        Self: Sized
+    {
+        self
+    }
+
+    /// Applies a closure which returns a `Try` value to each element, stopping at the first
+    /// failure of the closure or the iterator.
+    fn try_for_each<F, R>(&mut self, mut f: F) -> Result<R, Self::Error> where
+        Self: Sized, F: FnMut(Self::Item) -> R, R: Try<Output = ()>
+    {
+        self.try_fold((), move |(), x| f(x))
+    }
+
+    /// Tests if every element of the iterator matches a predicate.
+    ///
+    /// Stops at the first element not matching the predicate, or at the first error.
+    fn all<F>(&mut self, mut f: F) -> Result<bool, Self::Error> where
+        Self: Sized, F: FnMut(Self::Item) -> bool
+    {
+        let flow = self.try_fold((), |(), x| {
+            if f(x) { ControlFlow::Continue(()) } else { ControlFlow::Break(()) }
+        })?;
+        Ok(flow.is_continue())
+    }
+
+    /// Tests if any element of the iterator matches a predicate.
+    ///
+    /// Stops at the first element matching the predicate, or at the first error.
+    fn any<F>(&mut self, mut f: F) -> Result<bool, Self::Error> where
+        Self: Sized, F: FnMut(Self::Item) -> bool
+    {
+        let flow = self.try_fold((), |(), x| {
+            if f(x) { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        })?;
+        Ok(flow.is_break())
+    }
+
+    /// Searches for an element of an iterator that satisfies a predicate.
+    ///
+    /// Stops at the first error and returns it.
+    fn find<P>(&mut self, mut predicate: P) -> Result<Option<Self::Item>, Self::Error> where
+        Self: Sized, P: FnMut(&Self::Item) -> bool
+    {
+        let flow = self.try_fold((), |(), x| {
+            if predicate(&x) { ControlFlow::Break(x) } else { ControlFlow::Continue(()) }
+        })?;
+        Ok(flow.break_value())
+    }
+
+    /// Applies a function to the elements of the iterator and returns the first non-`None`
+    /// result.
+    ///
+    /// Stops at the first error and returns it.
+    fn find_map<B, F>(&mut self, mut f: F) -> Result<Option<B>, Self::Error> where
+        Self: Sized, F: FnMut(Self::Item) -> Option<B>
+    {
+        let flow = self.try_fold((), |(), x| match f(x) {
+            Some(y) => ControlFlow::Break(y),
+            None => ControlFlow::Continue(()),
+        })?;
+        Ok(flow.break_value())
+    }
+
+    /// Searches for an element in an iterator, returning its index.
+    ///
+    /// Stops at the first error and returns it.
+    fn position<P>(&mut self, mut predicate: P) -> Result<Option<usize>, Self::Error> where
+        Self: Sized, P: FnMut(Self::Item) -> bool
+    {
+        let flow = self.try_fold(0, |i, x| {
+            if predicate(x) { ControlFlow::Break(i) } else { ControlFlow::Continue(i + 1) }
+        })?;
+        Ok(flow.break_value())
+    }
+
+    /// Returns the `n`th element of the iterator, counting from zero.
+    ///
+    /// Stops at the first error and returns it.
+    fn nth(&mut self, n: usize) -> Result<Option<Self::Item>, Self::Error> {
+        for _ in 0..n {
+            if self.next()?.is_none() {
+                return Ok(None);
+            }
+        }
+        self.next()
+    }
+
+    /// Consumes the iterator, counting the number of elements.
+    fn count(self) -> Result<usize, Self::Error> where
+        Self: Sized
+    {
+        self.fold(0, |count, _| count + 1)
+    }
+
+    /// Consumes the iterator, returning the last element.
+    fn last(self) -> Result<Option<Self::Item>, Self::Error> where
+        Self: Sized
+    {
+        self.fold(None, |_, x| Some(x))
+    }
+
+    /// Returns the minimum element of the iterator.
+    fn min(self) -> Result<Option<Self::Item>, Self::Error> where
+        Self: Sized, Self::Item: Ord
+    {
+        shunt(self, |it| it.min())
+    }
+
+    /// Returns the maximum element of the iterator.
+    fn max(self) -> Result<Option<Self::Item>, Self::Error> where
+        Self: Sized, Self::Item: Ord
+    {
+        shunt(self, |it| it.max())
+    }
+
+    /// Returns the element that gives the minimum value from the specified function.
+    fn min_by_key<B, F>(self, f: F) -> Result<Option<Self::Item>, Self::Error> where
+        Self: Sized, B: Ord, F: FnMut(&Self::Item) -> B
+    {
+        shunt(self, |it| it.min_by_key(f))
+    }
+
+    /// Returns the element that gives the maximum value from the specified function.
+    fn max_by_key<B, F>(self, f: F) -> Result<Option<Self::Item>, Self::Error> where
+        Self: Sized, B: Ord, F: FnMut(&Self::Item) -> B
+    {
+        shunt(self, |it| it.max_by_key(f))
+    }
+
+    /// Returns the element that gives the minimum value with respect to the specified comparison
+    /// function.
+    fn min_by<F>(self, compare: F) -> Result<Option<Self::Item>, Self::Error> where
+        Self: Sized, F: FnMut(&Self::Item, &Self::Item) -> cmp::Ordering
+    {
+        shunt(self, |it| it.min_by(compare))
+    }
+
+    /// Returns the element that gives the maximum value with respect to the specified comparison
+    /// function.
+    fn max_by<F>(self, compare: F) -> Result<Option<Self::Item>, Self::Error> where
+        Self: Sized, F: FnMut(&Self::Item, &Self::Item) -> cmp::Ordering
+    {
+        shunt(self, |it| it.max_by(compare))
+    }
+
+    /// Converts an iterator of pairs into a pair of containers.
+    ///
+    /// Stops at the first error and returns it.
+    fn unzip<A, B, FromA, FromB>(self) -> Result<(FromA, FromB), Self::Error> where
+        Self: Sized + Iterrator<Item = (A, B)>,
+        FromA: Default + Extend<A>,
+        FromB: Default + Extend<B>
+    {
+        shunt(self, |it| it.unzip())
+    }
+
+    /// Sums the elements of an iterator.
+    fn sum<S>(self) -> Result<S, Self::Error> where
+        Self: Sized, S: Sum<Self::Item>
+    {
+        shunt(self, |it| it.sum())
+    }
+
+    /// Iterates over the entire iterator, multiplying all the elements.
+    fn product<P>(self) -> Result<P, Self::Error> where
+        Self: Sized, P: Product<Self::Item>
+    {
+        shunt(self, |it| it.product())
+    }
+}
+
+impl<I> Iterrator for &mut I where I: Iterrator + ?Sized{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        (**self).next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Result<Option<I::Item>, I::Error> {
+        (**self).nth(n)
+    }
+}
+
+/// The outcome of a closure passed to `Iterrator::try_fold`, telling it whether to continue.
+///
+/// This mirrors the unstable `std::ops::Try` and is implemented for `Result`, `Option` and
+/// `ControlFlow`.
+pub trait Try: Sized{
+    /// The value to continue with
+    type Output;
+    /// The value to stop with
+    type Residual;
+
+    /// Wraps a value to continue with.
+    fn from_output(output: Self::Output) -> Self;
+
+    /// Wraps a value to stop with.
+    fn from_residual(residual: Self::Residual) -> Self;
+
+    /// Decides whether to continue or to stop.
+    fn branch(self) -> ControlFlow<Self::Residual, Self::Output>;
+}
+
+impl<T, E> Try for Result<T, E>{
+    type Output = T;
+    type Residual = E;
+
+    fn from_output(output: T) -> Self {
+        Ok(output)
+    }
+
+    fn from_residual(residual: E) -> Self {
+        Err(residual)
+    }
+
+    fn branch(self) -> ControlFlow<E, T> {
+        match self {
+            Ok(output) => ControlFlow::Continue(output),
+            Err(residual) => ControlFlow::Break(residual),
+        }
+    }
+}
+
+impl<T> Try for Option<T>{
+    type Output = T;
+    type Residual = ();
+
+    fn from_output(output: T) -> Self {
+        Some(output)
+    }
+
+    fn from_residual((): ()) -> Self {
+        None
+    }
+
+    fn branch(self) -> ControlFlow<(), T> {
+        match self {
+            Some(output) => ControlFlow::Continue(output),
+            None => ControlFlow::Break(()),
+        }
+    }
+}
+
+impl<B, C> Try for ControlFlow<B, C>{
+    type Output = C;
+    type Residual = B;
+
+    fn from_output(output: C) -> Self {
+        ControlFlow::Continue(output)
+    }
+
+    fn from_residual(residual: B) -> Self {
+        ControlFlow::Break(residual)
+    }
+
+    fn branch(self) -> ControlFlow<B, C> {
+        self
+    }
+}
+
+/// Conversion from an `Iterrator`.
+///
+/// Implement this trait for a collection to enable `Iterrator::collect` to create it.
+pub trait FromIterrator<A>: Sized{
+    /// Creates a value from an `Iterrator`, failing with the first error of `iter`.
+    fn from_iterrator<I>(iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = A>;
+}
+
+/// An `Iterrator` which knows its exact length.
+///
+/// Implementors must return the same lower and upper bound from `size_hint`.
+pub trait ExactSizeIterrator: Iterrator{
+    /// Returns the exact remaining length of the iterator.
+    fn len(&self) -> usize {
+        let (lower, upper) = self.size_hint();
+        debug_assert_eq!(upper, Some(lower));
+        lower
+    }
+
+    /// Returns `true` if the iterator is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// An `Iterrator` which may be polled again after returning an error.
+///
+/// An error does not end the iteration of a resumable iterator. The next call to `next` continues
+/// with the elements after the failed one, just like a `std::iter::Iterator` over `Result`s does.
+/// Sources for which an error is terminal, e.g. because a connection broke, must not implement
+/// this trait. Adaptors implement it if the iterators they advance do.
+pub trait ResumableIterrator: Iterrator{}
+
+/// An `Iterrator` that keeps returning `Ok(None)` once it returned `Ok(None)` or an error.
+///
+/// Calling `fuse` on an iterator implementing this trait is unnecessary.
+pub trait FusedIterrator: Iterrator{}
+
+/// An `Iterrator` able to yield elements from both ends.
+pub trait DoubleEndedIterrator: Iterrator{
+    /// Removes and returns an element from the end of the iterator
+    ///
+    /// Fails and returns values just like `next`.
+    fn next_back(&mut self) -> Result<Option<Self::Item>, Self::Error>;
+
+    /// An iterator adaptor that applies a function starting from the back, producing a single,
+    /// final value.
+    fn rfold<B, F>(mut self, init: B, mut f: F) -> Result<B, Self::Error> where
+        Self: Sized, F: FnMut(B, Self::Item) -> B
+    {
+        let mut accum = init;
+        while let Some(x) = self.next_back()?{
+            accum = f(accum, x);
+        }
+        Ok(accum)
+    }
+
+    /// Searches for an element of an iterator from the back that satisfies a predicate.
+    ///
+    /// Stops at the first error and returns it.
+    fn rfind<P>(&mut self, mut predicate: P) -> Result<Option<Self::Item>, Self::Error> where
+        Self: Sized, P: FnMut(&Self::Item) -> bool
+    {
+        while let Some(x) = self.next_back()?{
+            if predicate(&x) {
+                return Ok(Some(x));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<I> Iterrator for Take<I> where I: Iterrator{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>, Self::Error> {
+        if self.n != 0 {
+            self.n -= 1;
+            self.iter.next()
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+        let (lower, upper) = self.iter.size_hint();
+        let lower = cmp::min(lower, self.n);
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+        (lower, upper)
+    }
+
+    fn try_fold<B, F, R>(&mut self, init: B, mut f: F) -> Result<R, I::Error> where
+        F: FnMut(B, I::Item) -> R, R: Try<Output = B>
+    {
+        if self.n == 0 {
+            return Ok(R::from_output(init));
+        }
+        let n = &mut self.n;
+        let flow = self.iter.try_fold(init, |accum, x| {
+            *n -= 1;
+            let r = f(accum, x);
+            if *n == 0 {
+                // Stop without polling the underlying iterator any further
+                return ControlFlow::Break(r);
+            }
+            match r.branch() {
+                ControlFlow::Continue(c) => ControlFlow::Continue(c),
+                ControlFlow::Break(residual) => ControlFlow::Break(R::from_residual(residual)),
+            }
+        });
+        let flow = match flow {
+            Ok(flow) => flow,
+            Err(e) => {
+                // Like in `next`, the error counts as one of the `n` elements
+                *n -= 1;
+                return Err(e);
+            }
+        };
+        Ok(match flow {
+            ControlFlow::Continue(c) => R::from_output(c),
+            ControlFlow::Break(r) => r,
+        })
+    }
+}
+
+impl<I> Iterrator for Enumerate<I> where I: Iterrator{
+    type Item = (usize, I::Item);
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>, I::Error> {
+        match self.iter.next()? {
+            Some(x) => {
+                let i = self.count;
+                self.count += 1;
+                Ok(Some((i, x)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    // `nth` is not forwarded, so the elements consumed before an error are counted
+}
+
+impl<I> ExactSizeIterrator for Enumerate<I> where I: ExactSizeIterrator{}
+
+impl<I> FusedIterrator for Enumerate<I> where I: FusedIterrator{}
+
+impl<I> Iterrator for Skip<I> where I: Iterrator{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        self.nth(0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Errors among the skipped elements are returned as well, so only the lower bound shrinks
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_sub(self.n), upper)
+    }
+
+    fn nth(&mut self, n: usize) -> Result<Option<I::Item>, I::Error> {
+        // Skip one element at a time, so an error only counts as one of the skipped elements
+        while self.n > 0 {
+            self.n -= 1;
+            if self.iter.next()?.is_none() {
+                self.n = 0;
+                return Ok(None);
+            }
+        }
+        self.iter.nth(n)
+    }
+}
+
+impl<I> DoubleEndedIterrator for Skip<I> where I: DoubleEndedIterrator + ExactSizeIterrator{
+    fn next_back(&mut self) -> Result<Option<I::Item>, I::Error> {
+        if self.iter.len() > self.n {
+            self.iter.next_back()
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<I> FusedIterrator for Skip<I> where I: FusedIterrator{}
+
+impl<I> Iterrator for StepBy<I> where I: Iterrator{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        // Skip one element at a time, so an error only counts as one of the skipped elements
+        while self.skip > 0 {
+            self.skip -= 1;
+            if self.iter.next()?.is_none() {
+                self.skip = 0;
+                return Ok(None);
+            }
+        }
+        self.skip = self.step;
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Errors among the skipped elements are returned as well, so only the lower bound shrinks
+        let (lower, upper) = self.iter.size_hint();
+        let lower = match lower.checked_sub(self.skip) {
+            Some(n) if n > 0 => 1 + (n - 1) / (self.step + 1),
+            _ => 0,
+        };
+        (lower, upper)
+    }
+}
+
+impl<I> FusedIterrator for StepBy<I> where I: FusedIterrator{}
+
+impl<I, P> Iterrator for SkipWhile<I,P> where
+    I: Iterrator,
+    P: FnMut(&I::Item) -> bool
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        if self.flag {
+            return self.iter.next();
+        }
+        while let Some(x) = self.iter.next()? {
+            if !(self.predicate)(&x) {
+                self.flag = true;
+                return Ok(Some(x));
+            }
+        }
+        Ok(None)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.flag {
+            self.iter.size_hint()
+        } else {
+            (0, self.iter.size_hint().1)
+        }
+    }
+}
+
+impl<I, P> FusedIterrator for SkipWhile<I,P> where
+    I: FusedIterrator, P: FnMut(&I::Item) -> bool {}
+
+impl<I, P> Iterrator for TakeWhile<I,P> where
+    I: Iterrator,
+    P: FnMut(&I::Item) -> bool
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        if self.flag {
+            return Ok(None);
+        }
+        match self.iter.next()? {
+            Some(x) => if (self.predicate)(&x) {
+                Ok(Some(x))
+            } else {
+                self.flag = true;
+                Ok(None)
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.flag {
+            (0, Some(0))
+        } else {
+            (0, self.iter.size_hint().1)
+        }
+    }
+}
+
+impl<I, P> FusedIterrator for TakeWhile<I,P> where
+    I: FusedIterrator, P: FnMut(&I::Item) -> bool {}
+
+impl<B, I, P> Iterrator for MapWhile<I,P> where
+    I: Iterrator,
+    P: FnMut(I::Item) -> Option<B>
+{
+    type Item = B;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<B>, I::Error> {
+        match self.iter.next()? {
+            Some(x) => Ok((self.predicate)(x)),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I, P> Iterrator for TrySkipWhile<I,P> where
+    I: Iterrator,
+    P: FnMut(&I::Item) -> Result<bool, I::Error>
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        if self.flag {
+            return self.iter.next();
+        }
+        while let Some(x) = self.iter.next()? {
+            if !(self.predicate)(&x)? {
+                self.flag = true;
+                return Ok(Some(x));
+            }
+        }
+        Ok(None)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.flag {
+            self.iter.size_hint()
+        } else {
+            (0, self.iter.size_hint().1)
+        }
+    }
+}
+
+impl<I, P> Iterrator for TryTakeWhile<I,P> where
+    I: Iterrator,
+    P: FnMut(&I::Item) -> Result<bool, I::Error>
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        if self.flag {
+            return Ok(None);
+        }
+        match self.iter.next()? {
+            Some(x) => if (self.predicate)(&x)? {
+                Ok(Some(x))
+            } else {
+                self.flag = true;
+                Ok(None)
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.flag {
+            (0, Some(0))
+        } else {
+            (0, self.iter.size_hint().1)
+        }
+    }
+}
+
+impl<B, I, St, F> Iterrator for Scan<I,St,F> where
+    I: Iterrator,
+    F: FnMut(&mut St, I::Item) -> Option<B>
+{
+    type Item = B;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<B>, I::Error> {
+        match self.iter.next()? {
+            Some(x) => Ok((self.f)(&mut self.state, x)),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<B, I, St, F> Iterrator for TryScan<I,St,F> where
+    I: Iterrator,
+    F: FnMut(&mut St, I::Item) -> Result<Option<B>, I::Error>
+{
+    type Item = B;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<B>, I::Error> {
+        match self.iter.next()? {
+            Some(x) => (self.f)(&mut self.state, x),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<B, I, F> Iterrator for Map<I,F> where
+    I: Iterrator,
+    F: FnMut(I::Item) -> B
+{
+    type Item = B;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<B>, Self::Error> {
+        Ok(self.iter.next()?.map(&mut self.f))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn try_fold<Acc, G, R>(&mut self, init: Acc, mut g: G) -> Result<R, I::Error> where
+        G: FnMut(Acc, B) -> R, R: Try<Output = Acc>
+    {
+        let f = &mut self.f;
+        self.iter.try_fold(init, move |accum, x| g(accum, f(x)))
+    }
+}
+
+impl<B, I, F> Iterrator for AndThen<I,F> where
+    I: Iterrator,
+    F: FnMut(I::Item) -> Result<B, I::Error>
+{
+    type Item = B;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<B>, Self::Error> {
+        match self.iter.next()? {
+            Some(x) => (self.f)(x).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<B, E, I, F> Iterrator for TryMap<I,F> where
+    I: Iterrator,
+    I::Error: From<E>,
+    F: FnMut(I::Item) -> Result<B, E>
+{
+    type Item = B;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<B>, Self::Error> {
+        match self.iter.next()? {
+            Some(x) => Ok(Some((self.f)(x)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<E, I, F> Iterrator for MapErr<I,F> where
+    I: Iterrator,
+    F: FnMut(I::Error) -> E
+{
+    type Item = I::Item;
+    type Error = E;
+
+    fn next(&mut self) -> Result<Option<Self::Item>, E> {
+        self.iter.next().map_err(&mut self.f)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Result<Option<Self::Item>, E> {
+        self.iter.nth(n).map_err(&mut self.f)
+    }
+}
+
+impl<E, I> Iterrator for ErrInto<I,E> where
+    I: Iterrator,
+    E: From<I::Error>
+{
+    type Item = I::Item;
+    type Error = E;
+
+    fn next(&mut self) -> Result<Option<Self::Item>, E> {
+        self.iter.next().map_err(E::from)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Result<Option<Self::Item>, E> {
+        self.iter.nth(n).map_err(E::from)
+    }
+}
+
+impl<I, F> Inspect<I,F> where I: Iterrator, F: FnMut(&I::Item){
+    fn do_inspect(&mut self, next: Option<I::Item>) -> Option<I::Item> {
+        if let Some(ref x) = next {
+            (self.f)(x);
+        }
+        next
+    }
+}
+
+impl<I, F> Iterrator for Inspect<I,F> where
+    I: Iterrator,
+    F: FnMut(&I::Item)
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        let next = self.iter.next()?;
+        Ok(self.do_inspect(next))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, F> DoubleEndedIterrator for Inspect<I,F> where
+    I: DoubleEndedIterrator,
+    F: FnMut(&I::Item)
+{
+    fn next_back(&mut self) -> Result<Option<I::Item>, I::Error> {
+        let next = self.iter.next_back()?;
+        Ok(self.do_inspect(next))
+    }
+}
+
+impl<I, F> ExactSizeIterrator for Inspect<I,F> where I: ExactSizeIterrator, F: FnMut(&I::Item){}
+
+impl<I, F> FusedIterrator for Inspect<I,F> where I: FusedIterrator, F: FnMut(&I::Item){}
+
+impl<I, F> InspectErr<I,F> where I: Iterrator, F: FnMut(&I::Error){
+    fn do_inspect(&mut self, next: Result<Option<I::Item>, I::Error>) -> Result<Option<I::Item>, I::Error> {
+        if let Err(ref e) = next {
+            (self.f)(e);
+        }
+        next
+    }
+}
+
+impl<I, F> Iterrator for InspectErr<I,F> where
+    I: Iterrator,
+    F: FnMut(&I::Error)
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        let next = self.iter.next();
+        self.do_inspect(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Result<Option<I::Item>, I::Error> {
+        let next = self.iter.nth(n);
+        self.do_inspect(next)
+    }
+}
+
+impl<I, F> DoubleEndedIterrator for InspectErr<I,F> where
+    I: DoubleEndedIterrator,
+    F: FnMut(&I::Error)
+{
+    fn next_back(&mut self) -> Result<Option<I::Item>, I::Error> {
+        let next = self.iter.next_back();
+        self.do_inspect(next)
+    }
+}
+
+impl<I, F> ExactSizeIterrator for InspectErr<I,F> where I: ExactSizeIterrator, F: FnMut(&I::Error){}
+
+impl<I, F> FusedIterrator for InspectErr<I,F> where I: FusedIterrator, F: FnMut(&I::Error){}
+
+impl<T, E, I> Iterrator for Convert<I> where I: Iterator<Item = Result<T, E>>{
+    type Item = T;
+    type Error = E;
+
+    fn next(&mut self) -> Result<Option<T>, E> {
+        self.iter.next().map_or(Ok(None), |r| r.map(Some))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> Iterator for Iter<I> where I: Iterrator{
+    type Item = Result<I::Item, I::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.next() {
+            Ok(Some(x)) => Some(Ok(x)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, E> Iterrator for FromIter<I, E> where I: Iterator{
+    type Item = I::Item;
+    type Error = E;
+
+    fn next(&mut self) -> Result<Option<I::Item>, E> {
+        Ok(self.iter.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Result<Option<I::Item>, E> {
+        Ok(self.iter.nth(n))
+    }
+}
+
+impl<I> Iterator for UnwrapInfallible<I> where I: Iterrator<Error = Infallible>{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        match self.iter.next() {
+            Ok(x) => x,
+            Err(never) => match never {},
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Converts an iterator over `Result`s into an `Iterrator`.
+///
+/// Each `Ok(x)` yielded by `iter` is returned as `Ok(Some(x))` by `next`, each `Err(e)` as
+/// `Err(e)`.
+///
+/// ```
+/// use iterrator::Iterrator;
+///
+/// let lines = vec![Ok(1), Ok(2), Err("broken")];
+/// let it = iterrator::convert(lines);
+/// assert_eq!(it.fold(0, |a, b| a + b), Err("broken"));
+/// ```
+pub fn convert<T, E, I>(iter: I) -> Convert<I::IntoIter> where
+    I: IntoIterator<Item = Result<T, E>>
+{
+    Convert{iter: iter.into_iter()}
+}
+
+/// Lifts an infallible iterator into an `Iterrator` with the error type `E`.
+///
+/// Use `Infallible` as `E` if the `Iterrator` is not going to be combined with fallible sources.
+///
+/// ```
+/// use std::convert::Infallible;
+/// use iterrator::Iterrator;
+///
+/// let it = iterrator::from_iter::<Infallible, _>(1..4);
+/// assert_eq!(it.unwrap_infallible().collect::<Vec<_>>(), vec![1, 2, 3]);
+/// ```
+pub fn from_iter<E, I>(iter: I) -> FromIter<I::IntoIter, E> where
+    I: IntoIterator
+{
+    FromIter{iter: iter.into_iter(), _error: PhantomData}
+}
+
+impl<I> ExactSizeIterrator for Take<I> where I: ExactSizeIterrator{}
+
+impl<B, I, F> ExactSizeIterrator for Map<I,F> where
+    I: ExactSizeIterrator, F: FnMut(I::Item) -> B {}
+
+impl<B, I, F> ExactSizeIterrator for AndThen<I,F> where
+    I: ExactSizeIterrator, F: FnMut(I::Item) -> Result<B, I::Error> {}
+
+impl<B, E, I, F> ExactSizeIterrator for TryMap<I,F> where
+    I: ExactSizeIterrator, I::Error: From<E>, F: FnMut(I::Item) -> Result<B, E> {}
+
+impl<E, I, F> ExactSizeIterrator for MapErr<I,F> where
+    I: ExactSizeIterrator, F: FnMut(I::Error) -> E {}
+
+impl<E, I> ExactSizeIterrator for ErrInto<I,E> where I: ExactSizeIterrator, E: From<I::Error>{}
+
+impl<T, E, I> ExactSizeIterrator for Convert<I> where
+    I: ExactSizeIterator<Item = Result<T, E>> {}
+
+impl<I, E> ExactSizeIterrator for FromIter<I, E> where I: ExactSizeIterator{}
+
+impl<I> ExactSizeIterator for Iter<I> where I: ExactSizeIterrator{}
+
+impl<I> ExactSizeIterator for UnwrapInfallible<I> where
+    I: ExactSizeIterrator<Error = Infallible> {}
+
+impl<I> Iterrator for Rev<I> where I: DoubleEndedIterrator{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        self.iter.next_back()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> DoubleEndedIterrator for Rev<I> where I: DoubleEndedIterrator{
+    fn next_back(&mut self) -> Result<Option<I::Item>, I::Error> {
+        self.iter.next()
+    }
+}
+
+impl<I> ExactSizeIterrator for Rev<I> where I: DoubleEndedIterrator + ExactSizeIterrator{}
+
+impl<I> DoubleEndedIterrator for Take<I> where I: DoubleEndedIterrator + ExactSizeIterrator{
+    fn next_back(&mut self) -> Result<Option<I::Item>, I::Error> {
+        if self.n == 0 {
+            return Ok(None);
+        }
+        // Skip the elements at the back which are not part of the first `n`, dropping their
+        // errors since iterating forward would never reach them
+        for _ in 0..self.iter.len().saturating_sub(self.n) {
+            if let Ok(None) = self.iter.next_back() {
+                return Ok(None);
+            }
+        }
+        let next = self.iter.next_back();
+        if let Ok(None) = next {
+            self.n = 0;
+        } else {
+            self.n -= 1;
+        }
+        next
+    }
+}
+
+impl<B, I, F> DoubleEndedIterrator for Map<I,F> where
+    I: DoubleEndedIterrator,
+    F: FnMut(I::Item) -> B
+{
+    fn next_back(&mut self) -> Result<Option<B>, I::Error> {
+        Ok(self.iter.next_back()?.map(&mut self.f))
+    }
+}
+
+impl<B, I, F> DoubleEndedIterrator for AndThen<I,F> where
+    I: DoubleEndedIterrator,
+    F: FnMut(I::Item) -> Result<B, I::Error>
+{
+    fn next_back(&mut self) -> Result<Option<B>, I::Error> {
+        match self.iter.next_back()? {
+            Some(x) => (self.f)(x).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<B, E, I, F> DoubleEndedIterrator for TryMap<I,F> where
+    I: DoubleEndedIterrator,
+    I::Error: From<E>,
+    F: FnMut(I::Item) -> Result<B, E>
+{
+    fn next_back(&mut self) -> Result<Option<B>, I::Error> {
+        match self.iter.next_back()? {
+            Some(x) => Ok(Some((self.f)(x)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<E, I, F> DoubleEndedIterrator for MapErr<I,F> where
+    I: DoubleEndedIterrator,
+    F: FnMut(I::Error) -> E
+{
+    fn next_back(&mut self) -> Result<Option<I::Item>, E> {
+        self.iter.next_back().map_err(&mut self.f)
+    }
+}
+
+impl<E, I> DoubleEndedIterrator for ErrInto<I,E> where
+    I: DoubleEndedIterrator,
+    E: From<I::Error>
+{
+    fn next_back(&mut self) -> Result<Option<I::Item>, E> {
+        self.iter.next_back().map_err(E::from)
+    }
+}
+
+impl<T, E, I> DoubleEndedIterrator for Convert<I> where
+    I: DoubleEndedIterator<Item = Result<T, E>>
+{
+    fn next_back(&mut self) -> Result<Option<T>, E> {
+        self.iter.next_back().map_or(Ok(None), |r| r.map(Some))
+    }
+}
+
+impl<I, E> DoubleEndedIterrator for FromIter<I, E> where I: DoubleEndedIterator{
+    fn next_back(&mut self) -> Result<Option<I::Item>, E> {
+        Ok(self.iter.next_back())
+    }
+}
+
+impl<I> DoubleEndedIterator for Iter<I> where I: DoubleEndedIterrator{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.iter.next_back() {
+            Ok(Some(x)) => Some(Ok(x)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl<I> DoubleEndedIterator for UnwrapInfallible<I> where
+    I: DoubleEndedIterrator<Error = Infallible>
+{
+    fn next_back(&mut self) -> Option<I::Item> {
+        match self.iter.next_back() {
+            Ok(x) => x,
+            Err(never) => match never {},
+        }
+    }
+}
+
+impl<I, P> Iterrator for Filter<I,P> where
+    I: Iterrator,
+    P: FnMut(&I::Item) -> bool
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        while let Some(x) = self.iter.next()? {
+            if (self.predicate)(&x) {
+                return Ok(Some(x));
+            }
+        }
+        Ok(None)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I, P> DoubleEndedIterrator for Filter<I,P> where
+    I: DoubleEndedIterrator,
+    P: FnMut(&I::Item) -> bool
+{
+    fn next_back(&mut self) -> Result<Option<I::Item>, I::Error> {
+        while let Some(x) = self.iter.next_back()? {
+            if (self.predicate)(&x) {
+                return Ok(Some(x));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<B, I, F> Iterrator for FilterMap<I,F> where
+    I: Iterrator,
+    F: FnMut(I::Item) -> Option<B>
+{
+    type Item = B;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<B>, I::Error> {
+        while let Some(x) = self.iter.next()? {
+            if let Some(y) = (self.f)(x) {
+                return Ok(Some(y));
+            }
+        }
+        Ok(None)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<B, I, F> DoubleEndedIterrator for FilterMap<I,F> where
+    I: DoubleEndedIterrator,
+    F: FnMut(I::Item) -> Option<B>
+{
+    fn next_back(&mut self) -> Result<Option<B>, I::Error> {
+        while let Some(x) = self.iter.next_back()? {
+            if let Some(y) = (self.f)(x) {
+                return Ok(Some(y));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<I, P> Iterrator for TryFilter<I,P> where
+    I: Iterrator,
+    P: FnMut(&I::Item) -> Result<bool, I::Error>
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        while let Some(x) = self.iter.next()? {
+            if (self.predicate)(&x)? {
+                return Ok(Some(x));
+            }
+        }
+        Ok(None)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I, P> DoubleEndedIterrator for TryFilter<I,P> where
+    I: DoubleEndedIterrator,
+    P: FnMut(&I::Item) -> Result<bool, I::Error>
+{
+    fn next_back(&mut self) -> Result<Option<I::Item>, I::Error> {
+        while let Some(x) = self.iter.next_back()? {
+            if (self.predicate)(&x)? {
+                return Ok(Some(x));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<I> Peekable<I> where I: Iterrator{
+    /// Returns a reference to the next element without advancing past it.
+    ///
+    /// If advancing the underlying iterator fails, a reference to the error is returned. The
+    /// error stays buffered until it is consumed by `next`.
+    pub fn peek(&mut self) -> Result<Option<&I::Item>, &I::Error> {
+        let iter = &mut self.iter;
+        self.peeked.get_or_insert_with(|| iter.next()).as_ref().map(Option::as_ref)
+    }
+
+    /// Returns a mutable reference to the next element without advancing past it.
+    ///
+    /// Errors are buffered just like they are by `peek`.
+    pub fn peek_mut(&mut self) -> Result<Option<&mut I::Item>, &I::Error> {
+        let iter = &mut self.iter;
+        match self.peeked.get_or_insert_with(|| iter.next()) {
+            Ok(x) => Ok(x.as_mut()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Consumes and returns the next element if `func` returns `true` for it. Otherwise the
+    /// element stays buffered and `Ok(None)` is returned.
+    ///
+    /// An error is always consumed and returned, since there is no element to pass to `func`.
+    pub fn next_if<F>(&mut self, func: F) -> Result<Option<I::Item>, I::Error> where
+        F: FnOnce(&I::Item) -> bool
+    {
+        match self.next()? {
+            Some(x) => if func(&x) {
+                Ok(Some(x))
+            } else {
+                self.peeked = Some(Ok(Some(x)));
+                Ok(None)
+            },
+            None => {
+                self.peeked = Some(Ok(None));
+                Ok(None)
+            }
+        }
+    }
+
+    /// Consumes and returns the next element if it is equal to `expected`.
+    ///
+    /// Errors are handled just like they are by `next_if`.
+    pub fn next_if_eq<T>(&mut self, expected: &T) -> Result<Option<I::Item>, I::Error> where
+        T: ?Sized, I::Item: PartialEq<T>
+    {
+        self.next_if(|x| x == expected)
+    }
+}
+
+impl<I> Iterrator for Peekable<I> where I: Iterrator{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let peek_len = match self.peeked {
+            Some(Ok(None)) => return (0, Some(0)),
+            Some(_) => 1,
+            None => 0,
+        };
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_add(peek_len), upper.and_then(|x| x.checked_add(peek_len)))
+    }
+}
+
+impl<I> DoubleEndedIterrator for Peekable<I> where I: DoubleEndedIterrator{
+    fn next_back(&mut self) -> Result<Option<I::Item>, I::Error> {
+        match self.peeked {
+            Some(Ok(None)) => Ok(None),
+            Some(_) => match self.iter.next_back()? {
+                Some(x) => Ok(Some(x)),
+                None => self.next(),
+            },
+            None => self.iter.next_back(),
+        }
+    }
+}
+
+impl<I> ExactSizeIterrator for Peekable<I> where I: ExactSizeIterrator{}
+
+impl<I> Fuse<I> where I: Iterrator{
+    /// Forwards the outcome of advancing the underlying iterator, dropping it if iteration is
+    /// over.
+    fn fused(&mut self, next: Result<Option<I::Item>, I::Error>) -> Result<Option<I::Item>, I::Error> {
+        match next {
+            Ok(Some(x)) => Ok(Some(x)),
+            other => {
+                self.iter = None;
+                other
+            }
+        }
+    }
+}
+
+impl<I> Iterrator for Fuse<I> where I: Iterrator{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        let next = match self.iter {
+            Some(ref mut iter) => iter.next(),
+            None => return Ok(None),
+        };
+        self.fused(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.iter {
+            Some(ref iter) => iter.size_hint(),
+            None => (0, Some(0)),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Result<Option<I::Item>, I::Error> {
+        let next = match self.iter {
+            Some(ref mut iter) => iter.nth(n),
+            None => return Ok(None),
+        };
+        self.fused(next)
+    }
+}
+
+impl<I> DoubleEndedIterrator for Fuse<I> where I: DoubleEndedIterrator{
+    fn next_back(&mut self) -> Result<Option<I::Item>, I::Error> {
+        let next = match self.iter {
+            Some(ref mut iter) => iter.next_back(),
+            None => return Ok(None),
+        };
+        self.fused(next)
+    }
+}
+
+impl<I> ExactSizeIterrator for Fuse<I> where I: ExactSizeIterrator{}
+
+impl<I> FusedIterrator for Fuse<I> where I: Iterrator{}
+
+impl<I> FusedIterrator for Take<I> where I: FusedIterrator{}
+
+impl<B, I, F> FusedIterrator for Map<I,F> where I: FusedIterrator, F: FnMut(I::Item) -> B{}
+
+impl<E, I, F> FusedIterrator for MapErr<I,F> where I: FusedIterrator, F: FnMut(I::Error) -> E{}
+
+impl<E, I> FusedIterrator for ErrInto<I,E> where I: FusedIterrator, E: From<I::Error>{}
+
+impl<I, P> FusedIterrator for Filter<I,P> where I: FusedIterrator, P: FnMut(&I::Item) -> bool{}
+
+impl<B, I, F> FusedIterrator for FilterMap<I,F> where
+    I: FusedIterrator, F: FnMut(I::Item) -> Option<B> {}
+
+impl<I> FusedIterrator for Rev<I> where I: DoubleEndedIterrator + FusedIterrator{}
+
+impl<I> FusedIterrator for Peekable<I> where I: FusedIterrator{}
+
+impl<I, E> FusedIterrator for FromIter<I, E> where I: FusedIterator{}
+
+impl<I> FusedIterator for Iter<I> where I: FusedIterrator{}
+
+impl<I> FusedIterator for UnwrapInfallible<I> where I: FusedIterrator<Error = Infallible>{}
+
+impl<A, B> Iterrator for Chain<A,B> where
+    A: Iterrator,
+    B: Iterrator<Item = A::Item, Error = A::Error>
+{
+    type Item = A::Item;
+    type Error = A::Error;
+
+    fn next(&mut self) -> Result<Option<A::Item>, A::Error> {
+        if let Some(ref mut a) = self.a {
+            if let Some(x) = a.next()? {
+                return Ok(Some(x));
+            }
+        }
+        self.a = None;
+        match self.b {
+            Some(ref mut b) => b.next(),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match (&self.a, &self.b) {
+            (Some(a), Some(b)) => {
+                let (a_lower, a_upper) = a.size_hint();
+                let (b_lower, b_upper) = b.size_hint();
+                let lower = a_lower.saturating_add(b_lower);
+                let upper = match (a_upper, b_upper) {
+                    (Some(x), Some(y)) => x.checked_add(y),
+                    _ => None,
+                };
+                (lower, upper)
+            }
+            (Some(a), None) => a.size_hint(),
+            (None, Some(b)) => b.size_hint(),
+            (None, None) => (0, Some(0)),
+        }
+    }
+}
+
+impl<A, B> DoubleEndedIterrator for Chain<A,B> where
+    A: DoubleEndedIterrator,
+    B: DoubleEndedIterrator<Item = A::Item, Error = A::Error>
+{
+    fn next_back(&mut self) -> Result<Option<A::Item>, A::Error> {
+        if let Some(ref mut b) = self.b {
+            if let Some(x) = b.next_back()? {
+                return Ok(Some(x));
+            }
+        }
+        self.b = None;
+        match self.a {
+            Some(ref mut a) => a.next_back(),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<A, B> Iterrator for Zip<A,B> where
+    A: Iterrator,
+    B: Iterrator<Error = A::Error>
+{
+    type Item = (A::Item, B::Item);
+    type Error = A::Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>, A::Error> {
+        let x = match self.a.next()? {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+        Ok(self.b.next()?.map(|y| (x, y)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, _) = self.b.size_hint();
+        // Every outcome consumes an element of `a`, but errors of `a` do not consume one of `b`
+        (cmp::min(a_lower, b_lower), a_upper)
+    }
+}
+
+impl<A, B> FusedIterrator for Zip<A,B> where
+    A: FusedIterrator,
+    B: FusedIterrator<Error = A::Error> {}
+
+/// Size hint of a flattening iterator, given the hints of the current inner and the outer
+/// iterator.
+fn flat_size_hint(
+    front: Option<(usize, Option<usize>)>,
+    outer: (usize, Option<usize>),
+) -> (usize, Option<usize>)
+{
+    let (lower, upper) = front.unwrap_or((0, Some(0)));
+    match outer {
+        (0, Some(0)) => (lower, upper),
+        _ => (lower, None),
+    }
+}
+
+impl<I, U, F> Iterrator for FlatMap<I,U,F> where
+    I: Iterrator,
+    U: Iterrator,
+    I::Error: From<U::Error>,
+    F: FnMut(I::Item) -> U
+{
+    type Item = U::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<U::Item>, I::Error> {
+        loop {
+            if let Some(ref mut front) = self.front {
+                if let Some(x) = front.next()? {
+                    return Ok(Some(x));
+                }
+            }
+            self.front = None;
+            match self.iter.next()? {
+                Some(x) => self.front = Some((self.f)(x)),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        flat_size_hint(self.front.as_ref().map(U::size_hint), self.iter.size_hint())
+    }
+}
+
+impl<I> Iterrator for Flatten<I> where
+    I: Iterrator,
+    I::Item: Iterrator,
+    I::Error: From<<I::Item as Iterrator>::Error>
+{
+    type Item = <I::Item as Iterrator>::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>, I::Error> {
+        loop {
+            if let Some(ref mut front) = self.front {
+                if let Some(x) = front.next()? {
+                    return Ok(Some(x));
+                }
+            }
+            self.front = None;
+            match self.iter.next()? {
+                Some(inner) => self.front = Some(inner),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        flat_size_hint(self.front.as_ref().map(Iterrator::size_hint), self.iter.size_hint())
+    }
+}
+
+impl<I, U, F> Iterrator for FlatMapIter<I,U,F> where
+    I: Iterrator,
+    U: IntoIterator,
+    F: FnMut(I::Item) -> U
+{
+    type Item = U::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<U::Item>, I::Error> {
+        loop {
+            if let Some(ref mut front) = self.front {
+                if let Some(x) = front.next() {
+                    return Ok(Some(x));
+                }
+            }
+            self.front = None;
+            match self.iter.next()? {
+                Some(x) => self.front = Some((self.f)(x).into_iter()),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        flat_size_hint(self.front.as_ref().map(Iterator::size_hint), self.iter.size_hint())
+    }
+}
+
+impl<I> Iterrator for FlattenIter<I> where I: Iterrator, I::Item: IntoIterator{
+    type Item = <I::Item as IntoIterator>::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>, I::Error> {
+        loop {
+            if let Some(ref mut front) = self.front {
+                if let Some(x) = front.next() {
+                    return Ok(Some(x));
+                }
+            }
+            self.front = None;
+            match self.iter.next()? {
+                Some(inner) => self.front = Some(inner.into_iter()),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        flat_size_hint(self.front.as_ref().map(Iterator::size_hint), self.iter.size_hint())
+    }
+}
+
+impl<I, U, F> FusedIterrator for FlatMapIter<I,U,F> where
+    I: FusedIterrator, U: IntoIterator, F: FnMut(I::Item) -> U {}
+
+impl<I> FusedIterrator for FlattenIter<I> where I: FusedIterrator, I::Item: IntoIterator{}
+
+impl<I> Iterrator for ChainAll<I> where I: Iterator, I::Item: Iterrator{
+    type Item = <I::Item as Iterrator>::Item;
+    type Error = <I::Item as Iterrator>::Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            if let Some(ref mut current) = self.current {
+                if let Some(x) = current.next()? {
+                    return Ok(Some(x));
+                }
+            }
+            match self.iters.next() {
+                Some(next) => self.current = Some(next),
+                None => {
+                    self.current = None;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = match self.current {
+            Some(ref current) => current.size_hint(),
+            None => (0, Some(0)),
+        };
+        match self.iters.size_hint() {
+            (_, Some(0)) => (lower, upper),
+            _ => (lower, None),
+        }
+    }
+}
+
+/// Creates an iterator over all the elements of the iterators in `iters`, in sequence.
+///
+/// Just like `Iterrator::chain`, an error does not end the iterator it stems from.
+pub fn chain_all<I>(iters: I) -> ChainAll<I::IntoIter> where
+    I: IntoIterator, I::Item: Iterrator
+{
+    ChainAll{iters: iters.into_iter(), current: None}
+}
+
+impl<I> Iterrator for SkipErrors<I> where I: ResumableIterrator{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        loop {
+            if let Ok(next) = self.iter.next() {
+                return Ok(next);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I, F> Iterrator for Recover<I,F> where
+    I: ResumableIterrator,
+    F: FnMut(I::Error) -> Result<Option<I::Item>, I::Error>
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        loop {
+            match self.iter.next() {
+                Ok(next) => return Ok(next),
+                Err(e) => if let Some(x) = (self.f)(e)? {
+                    return Ok(Some(x));
+                },
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<E, I, F> Iterrator for OrElse<I,F> where
+    I: Iterrator,
+    F: FnMut(I::Error) -> Result<I::Item, E>
+{
+    type Item = I::Item;
+    type Error = E;
+
+    fn next(&mut self) -> Result<Option<I::Item>, E> {
+        match self.iter.next() {
+            Ok(next) => Ok(next),
+            Err(e) => (self.f)(e).map(Some),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<E, I, F> DoubleEndedIterrator for OrElse<I,F> where
+    I: DoubleEndedIterrator,
+    F: FnMut(I::Error) -> Result<I::Item, E>
+{
+    fn next_back(&mut self) -> Result<Option<I::Item>, E> {
+        match self.iter.next_back() {
+            Ok(next) => Ok(next),
+            Err(e) => (self.f)(e).map(Some),
+        }
+    }
+}
+
+impl<E, I, F> ExactSizeIterrator for OrElse<I,F> where
+    I: ExactSizeIterrator, F: FnMut(I::Error) -> Result<I::Item, E> {}
+
+impl<T, E, I> ResumableIterrator for Convert<I> where I: Iterator<Item = Result<T, E>>{}
+
+impl<I, E> ResumableIterrator for FromIter<I, E> where I: Iterator{}
+
+impl<I> ResumableIterrator for &mut I where I: ResumableIterrator + ?Sized{}
+
+impl<I> ResumableIterrator for Take<I> where I: ResumableIterrator{}
+
+impl<I> ResumableIterrator for Enumerate<I> where I: ResumableIterrator{}
+
+impl<I> ResumableIterrator for Rev<I> where I: DoubleEndedIterrator + ResumableIterrator{}
+
+impl<I> ResumableIterrator for Peekable<I> where I: ResumableIterrator{}
+
+impl<B, I, F> ResumableIterrator for Map<I,F> where
+    I: ResumableIterrator, F: FnMut(I::Item) -> B {}
+
+impl<B, I, F> ResumableIterrator for AndThen<I,F> where
+    I: ResumableIterrator, F: FnMut(I::Item) -> Result<B, I::Error> {}
+
+impl<B, E, I, F> ResumableIterrator for TryMap<I,F> where
+    I: ResumableIterrator, I::Error: From<E>, F: FnMut(I::Item) -> Result<B, E> {}
+
+impl<E, I, F> ResumableIterrator for MapErr<I,F> where
+    I: ResumableIterrator, F: FnMut(I::Error) -> E {}
+
+impl<E, I> ResumableIterrator for ErrInto<I,E> where I: ResumableIterrator, E: From<I::Error>{}
+
+impl<I, F> ResumableIterrator for Inspect<I,F> where I: ResumableIterrator, F: FnMut(&I::Item){}
+
+impl<I, F> ResumableIterrator for InspectErr<I,F> where
+    I: ResumableIterrator, F: FnMut(&I::Error) {}
+
+impl<I, P> ResumableIterrator for Filter<I,P> where
+    I: ResumableIterrator, P: FnMut(&I::Item) -> bool {}
+
+impl<B, I, F> ResumableIterrator for FilterMap<I,F> where
+    I: ResumableIterrator, F: FnMut(I::Item) -> Option<B> {}
+
+impl<I, P> ResumableIterrator for TryFilter<I,P> where
+    I: ResumableIterrator, P: FnMut(&I::Item) -> Result<bool, I::Error> {}
+
+impl<A, B> ResumableIterrator for Chain<A,B> where
+    A: ResumableIterrator,
+    B: ResumableIterrator<Item = A::Item, Error = A::Error> {}
+
+impl<I> ResumableIterrator for ChainAll<I> where I: Iterator, I::Item: ResumableIterrator{}
+
+impl<I, U, F> ResumableIterrator for FlatMap<I,U,F> where
+    I: ResumableIterrator, U: ResumableIterrator, I::Error: From<U::Error>, F: FnMut(I::Item) -> U {}
+
+impl<I> ResumableIterrator for Flatten<I> where
+    I: ResumableIterrator,
+    I::Item: ResumableIterrator,
+    I::Error: From<<I::Item as Iterrator>::Error> {}
+
+impl<I, U, F> ResumableIterrator for FlatMapIter<I,U,F> where
+    I: ResumableIterrator, U: IntoIterator, F: FnMut(I::Item) -> U {}
+
+impl<I> ResumableIterrator for FlattenIter<I> where I: ResumableIterrator, I::Item: IntoIterator{}
+
+impl<I> ResumableIterrator for SkipErrors<I> where I: ResumableIterrator{}
+
+impl<I, F> ResumableIterrator for Recover<I,F> where
+    I: ResumableIterrator, F: FnMut(I::Error) -> Result<Option<I::Item>, I::Error> {}
+
+impl<E, I, F> ResumableIterrator for OrElse<I,F> where
+    I: ResumableIterrator, F: FnMut(I::Error) -> Result<I::Item, E> {}
+
+/// A `std::iter::Iterator` over the elements of an `Iterrator` which ends at the first error,
+/// storing it in `error`.
+struct Shunt<'a, I> where I: Iterrator{
+    iter: I,
+    error: &'a mut Option<I::Error>,
+}
+
+impl<'a, I> Iterator for Shunt<'a, I> where I: Iterrator{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        match self.iter.next() {
+            Ok(x) => x,
+            Err(e) => {
+                *self.error = Some(e);
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.error.is_some() {
+            (0, Some(0))
+        } else {
+            (0, self.iter.size_hint().1)
+        }
+    }
+}
+
+/// Runs `f` on a `std::iter::Iterator` over the elements of `iter`. Returns the first error
+/// of `iter` instead of the result of `f`, if any.
+fn shunt<I, B, F>(iter: I, f: F) -> Result<B, I::Error> where
+    I: Iterrator, F: FnOnce(&mut Shunt<I>) -> B
+{
+    let mut error = None;
+    let value = f(&mut Shunt{iter, error: &mut error});
+    match error {
+        Some(e) => Err(e),
+        None => Ok(value),
+    }
+}
+
+impl<T> FromIterrator<T> for Vec<T>{
+    fn from_iterrator<I>(mut iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = T>{
+        let mut vec = Vec::with_capacity(iter.size_hint().0);
+        while let Some(x) = iter.next()? {
+            vec.push(x);
+        }
+        Ok(vec)
+    }
+}
+
+impl<T> FromIterrator<T> for Box<[T]>{
+    fn from_iterrator<I>(iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = T>{
+        Vec::from_iterrator(iter).map(Vec::into_boxed_slice)
+    }
+}
+
+impl<T> FromIterrator<T> for VecDeque<T>{
+    fn from_iterrator<I>(iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = T>{
+        Vec::from_iterrator(iter).map(VecDeque::from)
+    }
+}
+
+impl<T> FromIterrator<T> for LinkedList<T>{
+    fn from_iterrator<I>(iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = T>{
+        shunt(iter, |it| it.collect())
+    }
+}
+
+impl<T> FromIterrator<T> for BinaryHeap<T> where T: Ord{
+    fn from_iterrator<I>(iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = T>{
+        Vec::from_iterrator(iter).map(BinaryHeap::from)
+    }
+}
+
+impl<T> FromIterrator<T> for BTreeSet<T> where T: Ord{
+    fn from_iterrator<I>(iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = T>{
+        shunt(iter, |it| it.collect())
+    }
+}
+
+impl<K, V> FromIterrator<(K, V)> for BTreeMap<K, V> where K: Ord{
+    fn from_iterrator<I>(iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = (K, V)>{
+        shunt(iter, |it| it.collect())
+    }
+}
+
+impl<T, S> FromIterrator<T> for HashSet<T, S> where T: Eq + Hash, S: BuildHasher + Default{
+    fn from_iterrator<I>(iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = T>{
+        shunt(iter, |it| it.collect())
+    }
+}
+
+impl<K, V, S> FromIterrator<(K, V)> for HashMap<K, V, S> where
+    K: Eq + Hash, S: BuildHasher + Default
+{
+    fn from_iterrator<I>(iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = (K, V)>{
+        shunt(iter, |it| it.collect())
+    }
+}
+
+impl FromIterrator<char> for String{
+    fn from_iterrator<I>(iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = char>{
+        shunt(iter, |it| it.collect())
+    }
+}
+
+impl<'a> FromIterrator<&'a str> for String{
+    fn from_iterrator<I>(iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = &'a str>{
+        shunt(iter, |it| it.collect())
+    }
+}
+
+impl FromIterrator<String> for String{
+    fn from_iterrator<I>(iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = String>{
+        shunt(iter, |it| it.collect())
+    }
+}
+
+impl<'a> FromIterrator<Cow<'a, str>> for String{
+    fn from_iterrator<I>(iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = Cow<'a, str>>{
+        shunt(iter, |it| it.collect())
+    }
+}
+
+impl FromIterrator<()> for (){
+    fn from_iterrator<I>(iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = ()>{
+        iter.fold((), |(), ()| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_support::Scripted;
+
+    struct FailIterator;
+    impl Iterrator for FailIterator{
+        type Item = usize;
+        type Error = ();
+
+        fn next(&mut self) -> Result<Option<Self::Item>, Self::Error>{
+            Err(())
+        }
+    }
+
+    #[derive(Clone)]
+    struct NumbersIterator(usize);
+    impl Iterrator for NumbersIterator{
+        type Item = usize;
+        type Error = ();
+
+        fn next(&mut self) -> Result<Option<Self::Item>, Self::Error>{
+            let n = &mut self.0;
+            *n += 1;
+            Ok(Some(*n))
+        }
+    }
+
+    #[test]
+    fn fold_fail() {
+
+        let it = FailIterator;
+        assert_eq!(it.fold(0, |a,b| a + b), Err(()));
+    }
+
+    #[test]
+    fn sum() {
+
+        let it = NumbersIterator(0);
+        assert_eq!(it.take(5).fold(0, |a,b| a + b), Ok(15));
+    }
+
+    #[test]
+    fn and_then() {
+
+        let it = NumbersIterator(0);
+        assert_eq!(it.take(5).and_then(|n| Ok(n * 2)).fold(0, |a,b| a + b), Ok(30));
+    }
+
+    #[test]
+    fn and_then_fail() {
+
+        let mut calls = 0;
+        let it = NumbersIterator(0).and_then(|n| if n == 3 { Err(()) } else { Ok(n) });
+        let result = it.map(|n| { calls += 1; n }).fold(0, |a,b| a + b);
+        assert_eq!(result, Err(()));
+        // Iteration stops at the first error, so only the first two elements are seen
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn try_map_converts_error() {
+
+        struct OddNumber;
+        impl From<OddNumber> for () {
+            fn from(_: OddNumber) {}
+        }
+
+        let it = NumbersIterator(0).take(2).try_map(|n| if n % 2 == 0 { Ok(n) } else { Err(OddNumber) });
+        assert_eq!(it.fold(0, |a,b| a + b), Err(()));
+        let it = NumbersIterator(0).take(4).map(|n| n * 2).try_map(|n| if n % 2 == 0 { Ok(n) } else { Err(OddNumber) });
+        assert_eq!(it.fold(0, |a,b| a + b), Ok(20));
+    }
+
+    #[test]
+    fn map_err() {
+
+        let it = FailIterator.map_err(|()| "failed");
+        assert_eq!(it.fold(0, |a,b| a + b), Err("failed"));
+    }
+
+    #[test]
+    fn err_into() {
+
+        #[derive(Debug, PartialEq)]
+        struct Wrapped(());
+        impl From<()> for Wrapped {
+            fn from(e: ()) -> Self { Wrapped(e) }
+        }
+
+        let it = FailIterator.err_into::<Wrapped>();
+        assert_eq!(it.fold(0, |a,b| a + b), Err(Wrapped(())));
+    }
+
+    #[test]
+    fn convert_from_results() {
+
+        let it = convert(vec![Ok(1), Ok(2), Ok(3)]);
+        assert_eq!(it.fold(0, |a,b| a + b), Ok::<_, ()>(6));
+        let it = convert(vec![Ok(1), Err("second"), Ok(3)]);
+        assert_eq!(it.fold(0, |a,b| a + b), Err("second"));
+    }
+
+    #[test]
+    fn iterator_roundtrip() {
+
+        let results: Vec<_> = NumbersIterator(0).take(3).iterator().collect();
+        assert_eq!(results, vec![Ok(1), Ok(2), Ok(3)]);
 
-    fn next(&mut self) -> Result<Option<Self::Item>, Self::Error> {
-        if self.n != 0 {
-            self.n -= 1;
-            self.iter.next()
-        } else {
-            Ok(None)
+        let mut it = convert(vec![Ok(1), Err("second")]).iterator();
+        assert_eq!(it.next(), Some(Ok(1)));
+        assert_eq!(it.next(), Some(Err("second")));
+        assert_eq!(it.next(), None);
+
+        let mut sum = 0;
+        for n in NumbersIterator(0).take(3).iterator() {
+            sum += n.unwrap();
         }
+        assert_eq!(sum, 6);
     }
-}
 
-impl<B, I, F> Iterrator for Map<I,F> where
-    I: Iterrator,
-    F: FnMut(I::Item) -> B
-{
-    type Item = B;
-    type Error = I::Error;
+    #[test]
+    fn from_iter_with_error_type() {
 
-    fn next(&mut self) -> Result<Option<B>, Self::Error> {
-        Ok(self.iter.next()?.map(&mut self.f))
+        let it = from_iter(vec![1, 2, 3]).and_then(|n| if n < 3 { Ok(n) } else { Err("too large") });
+        assert_eq!(it.fold(0, |a,b| a + b), Err("too large"));
     }
-}
 
-#[cfg(test)]
-mod tests {
+    #[test]
+    fn unwrap_infallible() {
 
-    use super::*;
+        let it = from_iter::<Infallible, _>(1..4).map(|n| n * 2);
+        assert_eq!(it.unwrap_infallible().collect::<Vec<_>>(), vec![2, 4, 6]);
+    }
 
-    struct FailIterator;
-    impl Iterrator for FailIterator{
-        type Item = usize;
-        type Error = ();
+    #[test]
+    fn collect() {
 
-        fn next(&mut self) -> Result<Option<Self::Item>, Self::Error>{
-            Err(())
+        let it = NumbersIterator(0).take(3);
+        assert_eq!(it.clone().collect::<Vec<_>>(), Ok(vec![1, 2, 3]));
+        assert_eq!(it.clone().map(|n| (n, n * n)).collect::<HashMap<_, _>>().unwrap()[&3], 9);
+        assert_eq!(it.map(|n: usize| n.to_string()).collect::<String>(), Ok("123".to_owned()));
+    }
+
+    #[test]
+    fn collect_stops_at_first_error() {
+
+        let mut polled = 0;
+        let it = convert(vec![Ok(3), Ok(1), Err("broken"), Ok(2)]).map(|n| { polled += 1; n });
+        assert_eq!(it.collect::<BTreeSet<_>>(), Err("broken"));
+        assert_eq!(polled, 2);
+    }
+
+    #[test]
+    fn collect_into_std() {
+
+        use std::rc::Rc;
+        use std::sync::Arc;
+
+        let it = convert(vec![Ok(1), Ok(2)]).map(|n: usize| n * 2);
+        assert_eq!(it.collect_into_std::<Rc<[_]>>(), Ok::<_, ()>(Rc::from(&[2, 4][..])));
+        let it = convert(vec![Ok('a'), Ok('b')]);
+        assert_eq!(it.collect_into_std::<Box<str>>(), Ok::<_, ()>(Box::from("ab")));
+        let it = convert(vec![Ok(1), Err("broken"), Ok(2)]);
+        assert_eq!(it.collect_into_std::<Arc<[_]>>(), Err("broken"));
+    }
+
+    #[test]
+    fn collect_into_custom_container() {
+
+        struct Evens(Vec<usize>);
+        impl FromIterrator<usize> for Evens {
+            fn from_iterrator<I>(iter: I) -> Result<Self, I::Error> where I: Iterrator<Item = usize> {
+                iter.fold(Vec::new(), |mut v, n| { if n % 2 == 0 { v.push(n) } v }).map(Evens)
+            }
         }
+
+        let evens: Evens = NumbersIterator(0).take(6).collect().unwrap();
+        assert_eq!(evens.0, vec![2, 4, 6]);
     }
 
-    struct NumbersIterator(usize);
-    impl Iterrator for NumbersIterator{
-        type Item = usize;
-        type Error = ();
+    #[test]
+    fn size_hint() {
 
-        fn next(&mut self) -> Result<Option<Self::Item>, Self::Error>{
-            let n = &mut self.0;
-            *n += 1;
-            Ok(Some(*n))
+        assert_eq!(FailIterator.size_hint(), (0, None));
+        assert_eq!(NumbersIterator(0).take(5).map(|n| n * 2).size_hint(), (0, Some(5)));
+        assert_eq!(from_iter::<(), _>(0..3).take(5).size_hint(), (3, Some(3)));
+        assert_eq!(from_iter::<(), _>(0..10).take(5).size_hint(), (5, Some(5)));
+        assert_eq!(from_iter::<(), _>(0..10).take(0).size_hint(), (0, Some(0)));
+        assert_eq!(convert(vec![Ok(1), Err(())]).iterator().size_hint(), (2, Some(2)));
+    }
+
+    #[test]
+    fn exact_size() {
+
+        let mut it = from_iter::<(), _>(vec![1, 2, 3]).map(|n| n + 1).take(2);
+        assert_eq!(it.len(), 2);
+        it.next().unwrap();
+        assert_eq!(it.len(), 1);
+        assert_eq!(it.iterator().len(), 1);
+    }
+
+    #[test]
+    fn rev() {
+
+        let it = from_iter::<(), _>(1..6).map(|n| n * 10).take(3).rev();
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![30, 20, 10]));
+        let it = convert(vec![Ok(1), Ok(2), Ok(3)]).rev().map(|n: u32| n.to_string());
+        assert_eq!(it.collect::<String>(), Ok::<_, ()>("321".to_owned()));
+    }
+
+    #[test]
+    fn take_rev_fail() {
+
+        // The error past the first two elements is never reached iterating forward
+        let mut it = convert(vec![Ok(1), Ok(2), Err("beyond")]).take(2);
+        assert_eq!(it.next_back(), Ok(Some(2)));
+        assert_eq!(it.next_back(), Ok(Some(1)));
+        assert_eq!(it.next_back(), Ok(None));
+        let mut it = convert(vec![Ok(1), Err("within"), Ok(3)]).take(2);
+        assert_eq!(it.next_back(), Err("within"));
+        assert_eq!(it.len(), 1);
+        assert_eq!(it.next_back(), Ok(Some(1)));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    fn rfold_fail() {
+
+        let it = convert(vec![Err("front"), Ok(2), Ok(3)]);
+        assert_eq!(it.rfold(0, |a,b| a + b), Err("front"));
+        let it = convert(vec![Ok(1), Ok(2), Ok(3)]);
+        assert_eq!(it.rfold(String::new(), |a,b: u32| a + &b.to_string()), Ok::<_, ()>("321".to_owned()));
+    }
+
+    #[test]
+    fn rfind() {
+
+        let mut it = convert(vec![Ok(1), Ok(2), Err("middle"), Ok(4), Ok(5)]);
+        assert_eq!(it.rfind(|&n| n % 2 == 0), Ok(Some(4)));
+        assert_eq!(it.rfind(|&n| n % 2 == 0), Err("middle"));
+        assert_eq!(it.rfind(|&n| n % 2 == 0), Ok(Some(2)));
+        assert_eq!(it.rfind(|&n| n % 2 == 0), Ok(None));
+    }
+
+    #[test]
+    fn filter() {
+
+        let it = NumbersIterator(0).take(6).filter(|n| n % 2 == 0);
+        assert_eq!(it.size_hint(), (0, Some(6)));
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![2, 4, 6]));
+        let it = FailIterator.filter(|_| true);
+        assert_eq!(it.collect::<Vec<_>>(), Err(()));
+    }
+
+    #[test]
+    fn filter_map() {
+
+        let it = convert(vec![Ok("1"), Ok("two"), Ok("3")]).filter_map(|s| s.parse::<u32>().ok());
+        assert_eq!(it.clone().collect::<Vec<_>>(), Ok::<_, ()>(vec![1, 3]));
+        assert_eq!(it.rev().collect::<Vec<_>>(), Ok(vec![3, 1]));
+    }
+
+    #[test]
+    fn try_filter() {
+
+        let it = NumbersIterator(0).take(6).try_filter(|&n| if n < 4 { Ok(n % 2 == 1) } else { Err(()) });
+        assert_eq!(it.collect::<Vec<_>>(), Err(()));
+        let it = NumbersIterator(0).take(3).try_filter(|&n| Ok(n % 2 == 1));
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![1, 3]));
+    }
+
+    #[test]
+    fn peek() {
+
+        let mut it = NumbersIterator(0).take(2).peekable();
+        assert_eq!(it.peek(), Ok(Some(&1)));
+        assert_eq!(it.peek(), Ok(Some(&1)));
+        assert_eq!(it.size_hint(), (1, Some(2)));
+        assert_eq!(it.next(), Ok(Some(1)));
+        *it.peek_mut().unwrap().unwrap() = 42;
+        assert_eq!(it.next(), Ok(Some(42)));
+        assert_eq!(it.peek(), Ok(None));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    fn peeked_error_is_returned_by_next() {
+
+        let mut it = convert(vec![Ok(1), Err("broken"), Ok(3)]).peekable();
+        assert_eq!(it.next(), Ok(Some(1)));
+        assert_eq!(it.peek(), Err(&"broken"));
+        // Peeking again does not advance past the error
+        assert_eq!(it.peek(), Err(&"broken"));
+        assert_eq!(it.peek_mut(), Err(&"broken"));
+        assert_eq!(it.next(), Err("broken"));
+        assert_eq!(it.peek(), Ok(Some(&3)));
+    }
+
+    #[test]
+    fn next_if() {
+
+        let mut it = convert(vec![Ok(1), Ok(2), Err("broken"), Ok(4)]).peekable();
+        assert_eq!(it.next_if(|&n| n == 1), Ok(Some(1)));
+        assert_eq!(it.next_if_eq(&3), Ok(None));
+        assert_eq!(it.next_if_eq(&2), Ok(Some(2)));
+        // An error is consumed regardless of the predicate
+        assert_eq!(it.next_if(|_| false), Err("broken"));
+        assert_eq!(it.next_if(|_| false), Ok(None));
+        assert_eq!(it.next(), Ok(Some(4)));
+        assert_eq!(it.next_if(|_| true), Ok(None));
+    }
+
+    #[test]
+    fn try_fold() {
+
+        let mut it = NumbersIterator(0).take(5);
+        assert_eq!(it.try_fold(0usize, |a, b| a.checked_add(b)), Ok(Some(15)));
+        let mut it = NumbersIterator(0).map(|n| n * 2);
+        let r: Result<Result<usize, usize>, ()> = it.try_fold(0, |a, b| if b > 6 { Err(b) } else { Ok(a + b) });
+        assert_eq!(r, Ok(Err(8)));
+        // The closure stopped the fold, so the iterator can be resumed
+        assert_eq!(it.next(), Ok(Some(10)));
+    }
+
+    #[test]
+    fn try_fold_fail() {
+
+        let mut it = Scripted::new(vec![Ok(Some(1)), Err("broken"), Ok(Some(3))]);
+        assert_eq!(it.try_fold(0, |a, b| Some(a + b)), Err("broken"));
+    }
+
+    #[test]
+    fn take_try_fold_does_not_overshoot() {
+
+        let mut inner = Scripted::new(vec![Ok(Some(1)), Ok(Some(2)), Err("beyond")]);
+        {
+            let mut it = inner.by_ref().take(2);
+            assert_eq!(it.try_fold(0, |a, b| ControlFlow::Continue::<(), _>(a + b)), Ok(ControlFlow::Continue(3)));
+            assert_eq!(it.next(), Ok(None));
         }
+        assert_eq!(inner.next(), Err("beyond"));
     }
 
     #[test]
-    fn fold_fail() {
+    fn take_try_fold_counts_errors() {
 
-        let it = FailIterator;
-        assert_eq!(it.fold(0, |a,b| a + b), Err(()));
+        let mut it = Scripted::new(vec![Err("broken"), Ok(Some(1)), Ok(Some(2)), Ok(Some(3))]).take(2);
+        assert_eq!(it.try_fold(0, |a, b| Some(a + b)), Err("broken"));
+        // Resuming after the failed `try_fold` yields no more elements than `next` would have
+        assert_eq!(it.next(), Ok(Some(1)));
+        assert_eq!(it.next(), Ok(None));
     }
 
     #[test]
-    fn sum() {
+    fn try_for_each() {
 
-        let it = NumbersIterator(0);
-        assert_eq!(it.take(5).fold(0, |a,b| a + b), Ok(15));
+        let mut seen = Vec::new();
+        let mut it = NumbersIterator(0);
+        let r = it.try_for_each(|n| if n < 3 { seen.push(n); Ok(()) } else { Err(n) });
+        assert_eq!(r, Ok(Err(3)));
+        assert_eq!(seen, vec![1, 2]);
+        assert_eq!(FailIterator.try_for_each(|_| Some(())), Err(()));
+    }
+
+    #[test]
+    fn short_circuiting_consumers() {
+
+        assert_eq!(NumbersIterator(0).take(3).all(|n| n < 4), Ok(true));
+        assert_eq!(NumbersIterator(0).all(|n| n < 4), Ok(false));
+        assert_eq!(NumbersIterator(0).any(|n| n == 4), Ok(true));
+        assert_eq!(NumbersIterator(0).take(3).any(|n| n == 4), Ok(false));
+        assert_eq!(NumbersIterator(0).find(|&n| n * n > 10), Ok(Some(4)));
+        assert_eq!(NumbersIterator(0).find_map(|n| if n > 2 { Some(n * 10) } else { None }), Ok(Some(30)));
+        assert_eq!(NumbersIterator(0).position(|n| n == 5), Ok(Some(4)));
+        assert_eq!(NumbersIterator(0).take(3).position(|n| n == 5), Ok(None));
+    }
+
+    #[test]
+    fn short_circuiting_consumers_fail() {
+
+        let source = || Scripted::new(vec![Ok(Some(1)), Err("broken"), Ok(Some(3))]);
+        assert_eq!(source().all(|n| n < 4), Err("broken"));
+        assert_eq!(source().any(|n| n == 3), Err("broken"));
+        assert_eq!(source().find(|&n| n == 3), Err("broken"));
+        assert_eq!(source().position(|n| n == 3), Err("broken"));
+        // An element satisfying the predicate before the error ends the search successfully
+        assert_eq!(source().find(|&n| n == 1), Ok(Some(1)));
+    }
+
+    #[test]
+    fn aggregates() {
+
+        let it = || NumbersIterator(0).take(4);
+        assert_eq!(it().count(), Ok(4));
+        assert_eq!(it().last(), Ok(Some(4)));
+        assert_eq!(it().min(), Ok(Some(1)));
+        assert_eq!(it().max(), Ok(Some(4)));
+        assert_eq!(it().min_by_key(|&n| (n as i64 - 3).abs()), Ok(Some(3)));
+        assert_eq!(it().max_by_key(|&n| n % 3), Ok(Some(2)));
+        assert_eq!(it().min_by(|a, b| b.cmp(a)), Ok(Some(4)));
+        assert_eq!(it().max_by(|a, b| b.cmp(a)), Ok(Some(1)));
+        assert_eq!(it().sum::<usize>(), Ok(10));
+        assert_eq!(it().product::<usize>(), Ok(24));
+        assert_eq!(it().take(0).max(), Ok(None));
+    }
+
+    #[test]
+    fn aggregates_fail() {
+
+        let source = || Scripted::new(vec![Ok(Some(1)), Err("broken"), Ok(Some(3))]);
+        assert_eq!(source().count(), Err("broken"));
+        assert_eq!(source().last(), Err("broken"));
+        assert_eq!(source().max(), Err("broken"));
+        assert_eq!(source().min_by_key(|&n| n), Err("broken"));
+        assert_eq!(source().sum::<usize>(), Err("broken"));
+        assert_eq!(source().product::<usize>(), Err("broken"));
+    }
+
+    #[test]
+    fn nth() {
+
+        let mut it = NumbersIterator(0);
+        assert_eq!(it.nth(2), Ok(Some(3)));
+        assert_eq!(it.nth(0), Ok(Some(4)));
+        let mut it = Scripted::new(vec![Ok(Some(1)), Err("broken"), Ok(Some(3))]);
+        assert_eq!(it.nth(2), Err("broken"));
+    }
+
+    #[test]
+    fn take_nth() {
+
+        let mut it = NumbersIterator(0).take(5);
+        assert_eq!(it.nth(1), Ok(Some(2)));
+        assert_eq!(it.size_hint(), (0, Some(3)));
+        assert_eq!(it.nth(3), Ok(None));
+        assert_eq!(it.next(), Ok(None));
+        // Skipping past the end of `Take` does not advance the underlying iterator any further
+        let mut inner = NumbersIterator(0);
+        assert_eq!(inner.by_ref().take(3).nth(10), Ok(None));
+        assert_eq!(inner.next(), Ok(Some(4)));
+    }
+
+    #[test]
+    fn take_nth_fail() {
+
+        let mut it = Scripted::new(vec![Ok(Some(1)), Err("broken"), Ok(Some(2)), Ok(Some(3))]).take(4);
+        assert_eq!(it.nth(3), Err("broken"));
+        // Only the two consumed outcomes count against the four elements
+        assert_eq!(it.size_hint(), (0, Some(2)));
+        assert_eq!(it.next(), Ok(Some(2)));
+        assert_eq!(it.next(), Ok(Some(3)));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    fn chain() {
+
+        let it = NumbersIterator(0).take(2).chain(NumbersIterator(10).take(2));
+        assert_eq!(it.size_hint(), (0, Some(4)));
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![1, 2, 11, 12]));
+        let it = from_iter::<(), _>(1..3).chain(from_iter(5..7)).rev();
+        assert_eq!(it.size_hint(), (4, Some(4)));
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![6, 5, 2, 1]));
+    }
+
+    #[test]
+    fn chain_fail() {
+
+        let first = Scripted::new(vec![Ok(Some(1)), Err("first"), Ok(Some(2))]);
+        let second = Scripted::new(vec![Err("second"), Ok(Some(3))]);
+        let results: Vec<_> = first.chain(second).iterator().collect();
+        assert_eq!(results, vec![Ok(1), Err("first"), Ok(2), Err("second"), Ok(3)]);
+        // Fused iterators end after their error, but the chain continues with the second one
+        let first = Scripted::new(vec![Err("first"), Ok(Some(1))]).fuse();
+        let mut it = first.chain(Scripted::new(vec![Ok(Some(7))]).fuse());
+        assert_eq!(it.next(), Err("first"));
+        assert_eq!(it.next(), Ok(Some(7)));
+    }
+
+    #[test]
+    fn chain_all() {
+
+        let shards = vec![
+            Scripted::new(vec![Ok(Some(1)), Ok(Some(2))]),
+            Scripted::new(vec![]),
+            Scripted::new(vec![Ok(Some(3)), Err("broken")]),
+        ];
+        let mut it = super::chain_all(shards);
+        assert_eq!(it.size_hint(), (0, None));
+        assert_eq!(it.by_ref().take(3).collect::<Vec<_>>(), Ok(vec![1, 2, 3]));
+        assert_eq!(it.next(), Err("broken"));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn zip() {
+
+        let it = NumbersIterator(0).zip(from_iter(vec!['a', 'b', 'c']));
+        assert_eq!(it.size_hint(), (0, None));
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![(1, 'a'), (2, 'b'), (3, 'c')]));
+        let it = from_iter::<(), _>(0..5).zip(from_iter(vec!['a', 'b', 'c']));
+        assert_eq!(it.size_hint(), (3, Some(5)));
+    }
+
+    #[test]
+    fn zip_error_precedence() {
+
+        // Error of the first iterator wins and the second one is not polled
+        let keys = Scripted::new(vec![Err("keys"), Ok(Some(2))]);
+        let values = Scripted::new(vec![Err("values"), Ok(Some(20))]);
+        let results: Vec<_> = keys.zip(values).iterator().collect();
+        assert_eq!(results, vec![Err("keys"), Err("values")]);
+        // The error does not consume the only element of the second iterator
+        let it = convert(vec![Err("keys"), Ok(1)]).zip(convert(vec![Ok(7)]));
+        assert_eq!(it.size_hint(), (1, Some(2)));
+        let results: Vec<_> = it.iterator().collect();
+        assert_eq!(results, vec![Err("keys"), Ok((1, 7))]);
+
+        // Error of the second iterator drops the element of the first one
+        let keys = Scripted::new(vec![Ok(Some(1)), Ok(Some(2))]);
+        let values = Scripted::new(vec![Err("values"), Ok(Some(20))]);
+        let results: Vec<_> = keys.zip(values).iterator().collect();
+        assert_eq!(results, vec![Err("values"), Ok((2, 20))]);
+
+        // The second iterator is not polled once the first one is exhausted
+        let mut values = Scripted::new(vec![Ok(Some(10)), Err("values")]);
+        assert_eq!(NumbersIterator(0).take(1).map_err(|()| "keys").zip(values.by_ref()).count(), Ok(1));
+        assert_eq!(values.next(), Err("values"));
+    }
+
+    #[test]
+    fn unzip() {
+
+        let it = NumbersIterator(0).take(3).map(|n| (n, n * n));
+        assert_eq!(it.unzip::<_, _, Vec<_>, Vec<_>>(), Ok((vec![1, 2, 3], vec![1, 4, 9])));
+        let it = Scripted::new(vec![Ok(Some(1)), Err("broken")]).map(|n| (n, n));
+        assert_eq!(it.unzip::<_, _, Vec<_>, Vec<_>>(), Err("broken"));
+    }
+
+    #[test]
+    fn enumerate() {
+
+        let it = Scripted::new(vec![Ok(Some(10)), Err("broken"), Ok(Some(20))]).enumerate();
+        let results: Vec<_> = it.iterator().collect();
+        // Errors are not counted
+        assert_eq!(results, vec![Ok((0, 10)), Err("broken"), Ok((1, 20))]);
+        let mut it = from_iter::<(), _>(vec!['a', 'b', 'c']).enumerate();
+        assert_eq!(it.nth(1), Ok(Some((1, 'b'))));
+        assert_eq!(it.next(), Ok(Some((2, 'c'))));
+        assert_eq!(it.next(), Ok(None));
+        let mut it = Scripted::new(vec![Ok(Some(10)), Ok(Some(20)), Err("broken"), Ok(Some(30))]).enumerate();
+        assert_eq!(it.nth(3), Err("broken"));
+        assert_eq!(it.next(), Ok(Some((2, 30))));
+    }
+
+    #[test]
+    fn skip() {
+
+        let it = NumbersIterator(0).take(5).skip(2);
+        assert_eq!(it.size_hint(), (0, Some(5)));
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![3, 4, 5]));
+        let mut it = NumbersIterator(0).skip(2);
+        assert_eq!(it.nth(1), Ok(Some(4)));
+        let it = from_iter::<(), _>(1..6).skip(3).rev();
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![5, 4]));
+    }
+
+    #[test]
+    fn skip_fail() {
+
+        let mut it = Scripted::new(vec![Err("broken"), Ok(Some(1)), Ok(Some(2)), Ok(Some(3))]).skip(2);
+        assert_eq!(it.next(), Err("broken"));
+        // The error counts as one of the skipped elements, skipping continues with the next call
+        assert_eq!(it.next(), Ok(Some(2)));
+        assert_eq!(it.next(), Ok(Some(3)));
+        let it = convert(vec![Ok(0), Err("broken"), Ok(1), Ok(2), Ok(3)]).skip(2);
+        assert_eq!(it.size_hint(), (3, Some(5)));
+        // The error among the skipped elements is not reached from the back
+        assert_eq!(it.rev().collect::<Vec<_>>(), Ok(vec![3, 2, 1]));
+    }
+
+    #[test]
+    fn step_by() {
+
+        let it = NumbersIterator(0).step_by(3).take(3);
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![1, 4, 7]));
+        let it = from_iter::<(), _>(0..10).step_by(4);
+        assert_eq!(it.size_hint(), (3, Some(10)));
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![0, 4, 8]));
+    }
+
+    #[test]
+    fn step_by_fail() {
+
+        let mut it = convert(vec![Ok(0), Err("broken"), Ok(2), Ok(3), Ok(4), Ok(5)]).step_by(2);
+        assert_eq!(it.next(), Ok(Some(0)));
+        assert_eq!(it.size_hint(), (2, Some(5)));
+        // The error counts as the skipped element, the stride continues with the next call
+        assert_eq!(it.next(), Err("broken"));
+        assert_eq!(it.next(), Ok(Some(2)));
+        assert_eq!(it.next(), Ok(Some(4)));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    fn skip_while_and_take_while() {
+
+        let it = NumbersIterator(0).skip_while(|&n| n < 3).take_while(|&n| n < 6);
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![3, 4, 5]));
+        let it = NumbersIterator(0).map_while(|n| if n < 4 { Some(n * 10) } else { None });
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![10, 20, 30]));
+    }
+
+    #[test]
+    fn try_skip_while_and_try_take_while() {
+
+        let it = NumbersIterator(0).try_skip_while(|&n| Ok(n < 3)).try_take_while(|&n| Ok(n < 6));
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![3, 4, 5]));
+        let it = NumbersIterator(0).try_skip_while(|&n| if n < 3 { Ok(true) } else { Err(()) });
+        assert_eq!(it.collect::<Vec<_>>(), Err(()));
+        let mut it = NumbersIterator(0).try_take_while(|&n| if n < 3 { Ok(true) } else { Err(()) });
+        assert_eq!(it.by_ref().take(2).collect::<Vec<_>>(), Ok(vec![1, 2]));
+        assert_eq!(it.next(), Err(()));
+    }
+
+    #[test]
+    fn inspect() {
+
+        let mut seen = Vec::new();
+        let mut errors = Vec::new();
+        {
+            let it = Scripted::new(vec![Ok(Some(1)), Err("broken"), Ok(Some(2))])
+                .inspect(|&n| seen.push(n))
+                .inspect_err(|&e| errors.push(e))
+                .map(|n| n * 10);
+            let results: Vec<_> = it.iterator().collect();
+            assert_eq!(results, vec![Ok(10), Err("broken"), Ok(20)]);
+        }
+        assert_eq!(seen, vec![1, 2]);
+        assert_eq!(errors, vec!["broken"]);
+    }
+
+    #[test]
+    fn skip_errors() {
+
+        let it = convert(vec![Ok(1), Err("bad record"), Ok(3), Err("bad record")]).skip_errors();
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![1, 3]));
+    }
+
+    #[test]
+    fn recover() {
+
+        let mut skipped = 0;
+        let records = convert(vec![Ok(1), Err("skip"), Ok(3), Err("replace"), Err("fatal"), Ok(6)]);
+        let results: Vec<_> = records
+            .recover(|e| match e {
+                "skip" => { skipped += 1; Ok(None) }
+                "replace" => Ok(Some(0)),
+                other => Err(other),
+            })
+            .iterator()
+            .collect();
+        assert_eq!(results, vec![Ok(1), Ok(3), Ok(0), Err("fatal"), Ok(6)]);
+        assert_eq!(skipped, 1);
+    }
+
+    #[test]
+    fn or_else() {
+
+        let it = Scripted::new(vec![Ok(Some(1)), Err("broken"), Ok(Some(3))]);
+        let results: Vec<_> = it.or_else(|e| if e == "broken" { Ok(2) } else { Err(()) }).iterator().collect();
+        assert_eq!(results, vec![Ok(1), Ok(2), Ok(3)]);
+
+        // A fused iterator ends after the replaced error
+        let it = Scripted::new(vec![Ok(Some(1)), Err("broken"), Ok(Some(3))]).fuse();
+        assert_eq!(it.or_else(|_| Ok::<_, ()>(0)).collect::<Vec<_>>(), Ok(vec![1, 0]));
+    }
+
+    #[test]
+    fn collect_partitioned() {
+
+        let records = convert(vec![Ok(1), Err("first"), Ok(3), Err("second")]);
+        assert_eq!(records.collect_partitioned(), (vec![1, 3], vec!["first", "second"]));
+    }
+
+    #[test]
+    fn collect_all_errors() {
+
+        let records = convert(vec![Ok(1), Err("first"), Ok(3), Err("second")]);
+        assert_eq!(records.collect_all_errors::<Vec<_>>(), Err(vec!["first", "second"]));
+        let records = convert(vec![Ok(1), Ok(3)]);
+        assert_eq!(records.collect_all_errors::<BTreeSet<_>>(), Ok::<_, Vec<()>>(vec![1, 3].into_iter().collect()));
+    }
+
+    #[test]
+    fn scan() {
+
+        let it = NumbersIterator(0).scan(0, |total, n| { *total += n; if *total < 12 { Some(*total) } else { None } });
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![1, 3, 6, 10]));
+        let it = Scripted::new(vec![Ok(Some(1)), Err("broken")]).scan(0, |total, n| { *total += n; Some(*total) });
+        assert_eq!(it.collect::<Vec<_>>(), Err("broken"));
+    }
+
+    #[test]
+    fn try_scan_delta_decoding() {
+
+        // Each element is the difference to its predecessor. Decoding fails on underflow.
+        let decode = |deltas: Vec<i64>| convert(deltas.into_iter().map(Ok))
+            .try_scan(0u64, |last, delta: i64| {
+                let value = (*last as i64).checked_add(delta).filter(|&v| v >= 0).ok_or("underflow")?;
+                *last = value as u64;
+                Ok(Some(*last))
+            });
+        assert_eq!(decode(vec![5, 2, -3, 10]).collect::<Vec<_>>(), Ok(vec![5, 7, 4, 14]));
+        assert_eq!(decode(vec![5, -6, 1]).collect::<Vec<_>>(), Err("underflow"));
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum PageError {
+        Fetch(&'static str),
+        Decode(()),
+    }
+    impl From<()> for PageError {
+        fn from(e: ()) -> Self { PageError::Decode(e) }
+    }
+
+    #[test]
+    fn flat_map() {
+
+        let it = NumbersIterator(0).take(3).flat_map(|n| NumbersIterator(n * 10).take(n));
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![11, 21, 22, 31, 32, 33]));
+        let it = NumbersIterator(0).take(3).flat_map_iter(|n| vec![n; n]);
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![1, 2, 2, 3, 3, 3]));
+    }
+
+    #[test]
+    fn flat_map_unifies_errors() {
+
+        let pages = Scripted::new(vec![Ok(Some(1)), Err("fetch"), Ok(Some(2))]).map_err(PageError::Fetch);
+        let it = pages.flat_map(|page| if page == 1 { from_iter(vec![10, 11]) } else { from_iter(vec![]) }
+            .chain(convert(vec![Err(())])));
+        let results: Vec<_> = it.iterator().collect();
+        assert_eq!(results, vec![
+            Ok(10), Ok(11), Err(PageError::Decode(())),
+            Err(PageError::Fetch("fetch")),
+            Err(PageError::Decode(())),
+        ]);
+    }
+
+    #[test]
+    fn flatten() {
+
+        let nested = from_iter::<PageError, _>(vec![
+            convert(vec![Ok(1), Ok(2)]),
+            convert(vec![]),
+            convert(vec![Ok(3), Err(())]),
+        ]);
+        let mut it = nested.flatten();
+        assert_eq!(it.size_hint(), (0, None));
+        assert_eq!(it.by_ref().take(3).collect::<Vec<_>>(), Ok(vec![1, 2, 3]));
+        assert_eq!(it.next(), Err(PageError::Decode(())));
+        assert_eq!(it.next(), Ok(None));
+
+        let it = NumbersIterator(0).take(3).map(|n| vec![n, n * 10]).flatten_iter();
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![1, 10, 2, 20, 3, 30]));
+    }
+
+    #[test]
+    fn fuse_after_end() {
+
+        let mut it = Scripted::new(vec![Ok(Some(1)), Ok(None), Ok(Some(2))]).fuse();
+        assert_eq!(it.next(), Ok(Some(1)));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn fuse_after_error() {
+
+        let mut it = Scripted::new(vec![Ok(Some(1)), Err("broken"), Ok(Some(2))]).fuse();
+        assert_eq!(it.next(), Ok(Some(1)));
+        assert_eq!(it.next(), Err("broken"));
+        assert_eq!(it.next(), Ok(None));
+
+        let mut it = Scripted::new(vec![Err("broken"), Ok(Some(2))]).fuse().take(2);
+        assert_eq!(it.next(), Err("broken"));
+        assert_eq!(it.next(), Ok(None));
     }
 }
@@ -0,0 +1,251 @@
+//! Applying a closure to the elements of an `Iterrator` on several threads.
+//!
+//! `Iterrator::par_map` creates a `ParMap` adaptor. It pulls the elements from the underlying
+//! iterator on the calling thread and hands them to a fixed number of worker threads, which apply
+//! the closure. The results are put back into the order of the elements before they are returned.
+//!
+//! This module requires the `parallel` feature.
+
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use super::Iterrator;
+
+type Outcome<B, E> = thread::Result<Result<B, E>>;
+
+/// An iterator that maps the elements of `iter` with `f` on worker threads.
+///
+/// This `struct` is created by the `par_map()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct ParMap<I, B> where I: Iterrator{
+    iter: I,
+    // `None` once the workers are told to stop
+    jobs: Option<Sender<(usize, I::Item)>>,
+    results: Receiver<(usize, Outcome<B, I::Error>)>,
+    // Results which arrived before the results of the preceding elements
+    reorder: BTreeMap<usize, Outcome<B, I::Error>>,
+    // Error of the underlying iterator, together with its position
+    error: Option<(usize, I::Error)>,
+    workers: Vec<JoinHandle<()>>,
+    cancelled: Arc<AtomicBool>,
+    // Position of the next element handed to the workers
+    sent: usize,
+    // Position of the next element to be returned
+    returned: usize,
+    // Maximum number of elements being processed or waiting in `reorder`
+    window: usize,
+    source_done: bool,
+    finished: bool,
+}
+
+impl<I, B> ParMap<I, B> where
+    I: Iterrator,
+    I::Item: Send + 'static,
+    I::Error: Send + 'static,
+    B: Send + 'static
+{
+    pub(crate) fn new<F>(iter: I, threads: usize, f: F) -> Self where
+        F: Fn(I::Item) -> Result<B, I::Error> + Send + Sync + 'static
+    {
+        assert!(threads != 0);
+        let (jobs, job_receiver) = channel::<(usize, I::Item)>();
+        let (result_sender, results) = channel();
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let f = Arc::new(f);
+        let workers = (0..threads).map(|_| {
+            let job_receiver = job_receiver.clone();
+            let result_sender = result_sender.clone();
+            let cancelled = cancelled.clone();
+            let f = f.clone();
+            thread::spawn(move || loop {
+                let job = job_receiver.lock().unwrap().recv();
+                let (index, item) = match job {
+                    Ok(job) => job,
+                    Err(_) => break,
+                };
+                if cancelled.load(Ordering::Relaxed) {
+                    break;
+                }
+                let outcome = panic::catch_unwind(AssertUnwindSafe(|| f(item)));
+                if result_sender.send((index, outcome)).is_err() {
+                    break;
+                }
+            })
+        }).collect();
+        ParMap{
+            iter,
+            jobs: Some(jobs),
+            results,
+            reorder: BTreeMap::new(),
+            error: None,
+            workers,
+            cancelled,
+            sent: 0,
+            returned: 0,
+            window: threads * 2,
+            source_done: false,
+            finished: false,
+        }
+    }
+}
+
+impl<I, B> ParMap<I, B> where I: Iterrator{
+    /// Tells the workers to skip the elements they have not started on yet and to stop.
+    fn cancel(&mut self) {
+        self.finished = true;
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.jobs = None;
+    }
+
+    /// Hands elements to the workers until the window is full, the underlying iterator is
+    /// exhausted or it failed.
+    fn fill(&mut self) {
+        while !self.source_done && self.error.is_none() && self.sent - self.returned < self.window {
+            match self.iter.next() {
+                Ok(Some(x)) => {
+                    // The workers only stop once `jobs` is dropped, so sending can not fail
+                    let _ = self.jobs.as_ref().unwrap().send((self.sent, x));
+                    self.sent += 1;
+                }
+                Ok(None) => self.source_done = true,
+                Err(e) => self.error = Some((self.sent, e)),
+            }
+        }
+    }
+}
+
+impl<I, B> Iterrator for ParMap<I, B> where I: Iterrator{
+    type Item = B;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<B>, I::Error> {
+        if self.finished {
+            return Ok(None);
+        }
+        self.fill();
+        loop {
+            if let Some(outcome) = self.reorder.remove(&self.returned) {
+                self.returned += 1;
+                match outcome {
+                    Ok(Ok(x)) => return Ok(Some(x)),
+                    Ok(Err(e)) => {
+                        self.cancel();
+                        return Err(e);
+                    }
+                    Err(payload) => {
+                        self.cancel();
+                        panic::resume_unwind(payload);
+                    }
+                }
+            }
+            if self.returned == self.sent {
+                return match self.error.take() {
+                    Some((_, e)) => {
+                        self.cancel();
+                        Err(e)
+                    }
+                    None => {
+                        self.finished = true;
+                        Ok(None)
+                    }
+                };
+            }
+            // Each worker sends a result for every element it received before `cancel`
+            let (index, outcome) = self.results.recv().expect("worker threads stopped unexpectedly");
+            self.reorder.insert(index, outcome);
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            return (0, Some(0));
+        }
+        let pending = self.sent - self.returned + self.error.is_some() as usize;
+        if self.source_done || self.error.is_some() {
+            return (pending, Some(pending));
+        }
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_add(pending), upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+impl<I, B> Drop for ParMap<I, B> where I: Iterrator{
+    fn drop(&mut self) {
+        self.cancel();
+        for worker in self.workers.drain(..) {
+            // Panics of the closure are caught within the workers
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+    use test_support::Scripted;
+
+    #[test]
+    fn preserves_order() {
+
+        // Earlier elements take longer, so their results arrive last
+        let it = Scripted::numbers(20).par_map(4, |n| {
+            thread::sleep(Duration::from_millis(20 - n as u64));
+            Ok(n * 2)
+        });
+        assert_eq!(it.collect::<Vec<_>>(), Ok((0..20).map(|n| n * 2).collect()));
+    }
+
+    #[test]
+    fn source_error_in_position() {
+
+        let mut it = Scripted::new(vec![Ok(Some(1)), Ok(Some(2)), Err("broken"), Ok(Some(3))])
+            .par_map(2, |n| Ok(n * 10));
+        assert_eq!(it.size_hint(), (0, None));
+        assert_eq!(it.next(), Ok(Some(10)));
+        assert_eq!(it.next(), Ok(Some(20)));
+        assert_eq!(it.next(), Err("broken"));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    fn closure_error_cancels() {
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let mut it = Scripted::numbers(1000).par_map(2, move |n| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(1));
+            if n == 3 { Err("failed") } else { Ok(n) }
+        });
+        assert_eq!(it.by_ref().collect::<Vec<_>>(), Err("failed"));
+        assert_eq!(it.next(), Ok(None));
+        drop(it);
+        // Only the elements within the window were handed to the workers
+        assert!(calls.load(Ordering::SeqCst) < 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "bad element")]
+    fn propagates_panic() {
+
+        let it = Scripted::numbers(10).par_map(3, |n| if n == 5 { panic!("bad element") } else { Ok(n) });
+        let _ = it.count();
+    }
+
+    #[test]
+    fn drop_early() {
+
+        let mut it = Scripted::numbers(100).par_map(4, |n| Ok(n + 1));
+        assert_eq!(it.next(), Ok(Some(1)));
+        // Joins the workers without processing the remaining elements
+        drop(it);
+    }
+}
@@ -0,0 +1,144 @@
+//! Reading ahead of the consumer of an `Iterrator` on a background thread.
+//!
+//! `Iterrator::prefetch` moves an iterator to a background thread, which keeps calling `next` and
+//! sends the outcomes through a bounded channel. The consumer only waits if the buffer ran empty.
+
+use std::panic;
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::thread::{self, JoinHandle};
+
+use super::Iterrator;
+
+/// An iterator that receives the elements of another iterator running on a background thread.
+///
+/// This `struct` is created by the `prefetch()` method on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct Prefetch<T, E>{
+    // `None` once the background thread is finished
+    receiver: Option<Receiver<Result<Option<T>, E>>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl<T, E> Prefetch<T, E> where T: Send + 'static, E: Send + 'static{
+    pub(crate) fn new<I>(mut iter: I, n: usize) -> Self where
+        I: Iterrator<Item = T, Error = E> + Send + 'static
+    {
+        let (sender, receiver) = sync_channel(n);
+        let worker = thread::spawn(move || loop {
+            let next = iter.next();
+            let last = !matches!(next, Ok(Some(_)));
+            // Sending fails once the `Prefetch` has been dropped
+            if sender.send(next).is_err() || last {
+                break;
+            }
+        });
+        Prefetch{receiver: Some(receiver), worker: Some(worker)}
+    }
+}
+
+impl<T, E> Prefetch<T, E>{
+    /// Waits for the background thread, propagating its panic.
+    fn join(&mut self) {
+        self.receiver = None;
+        if let Some(worker) = self.worker.take() {
+            if let Err(payload) = worker.join() {
+                panic::resume_unwind(payload);
+            }
+        }
+    }
+}
+
+impl<T, E> Iterrator for Prefetch<T, E>{
+    type Item = T;
+    type Error = E;
+
+    fn next(&mut self) -> Result<Option<T>, E> {
+        let next = match self.receiver {
+            Some(ref receiver) => receiver.recv(),
+            None => return Ok(None),
+        };
+        match next {
+            Ok(Ok(Some(x))) => Ok(Some(x)),
+            Ok(last) => {
+                self.join();
+                last
+            }
+            // The background thread ended without sending `Ok(None)` or an error, so it panicked
+            Err(_) => {
+                self.join();
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl<T, E> Drop for Prefetch<T, E>{
+    fn drop(&mut self) {
+        // Dropping the receiver makes the background thread stop once its current call to `next`
+        // returned
+        self.receiver = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+    use from_iter;
+    use test_support::Scripted;
+
+    #[test]
+    fn prefetch() {
+
+        let it = from_iter::<&'static str, _>(vec![1, 2, 3]).prefetch(2);
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn reads_ahead() {
+
+        let source = Scripted::numbers(100);
+        let calls = source.calls();
+        let mut it = source.prefetch(3);
+        assert_eq!(it.next(), Ok(Some(0)));
+        // One element is being sent while three more wait in the buffer
+        for _ in 0..100 {
+            if calls.load(Ordering::SeqCst) == 5 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        thread::sleep(Duration::from_millis(10));
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+        // Stops the background thread
+        drop(it);
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn stops_at_error() {
+
+        let source = Scripted::new(vec![Ok(Some(0)), Ok(Some(1)), Err("broken"), Ok(Some(3))]);
+        let calls = source.calls();
+        let mut it = source.prefetch(10);
+        assert_eq!(it.next(), Ok(Some(0)));
+        assert_eq!(it.next(), Ok(Some(1)));
+        assert_eq!(it.next(), Err("broken"));
+        assert_eq!(it.next(), Ok(None));
+        // The source is not called again after the error
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "source failed")]
+    fn propagates_panic() {
+
+        let it = from_iter::<(), _>(vec![1, 2]).map(|n| if n == 2 { panic!("source failed") } else { n });
+        let _ = it.prefetch(1).count();
+    }
+}
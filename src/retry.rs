@@ -0,0 +1,355 @@
+//! Retrying `Iterrator`s whose errors are transient.
+//!
+//! `Iterrator::retry` creates a `Retry` adaptor, which calls `next` of the underlying iterator
+//! again after it failed, as long as the `RetryPolicy` permits it. Between two attempts the
+//! adaptor waits for the delay chosen by the policy, using a `Sleeper`. The default sleeper blocks
+//! the current thread, tests may inject their own one to run without actually sleeping.
+//!
+//! If the underlying iterator returns `Ok(None)` after an error which is being retried, the error
+//! is returned. Retrying a `ResumableIterrator` is usually a mistake, see `Iterrator::retry`.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::thread;
+use std::time::Duration;
+
+use super::{FusedIterrator, Iterrator};
+
+/// Decides whether and when a failed call to `next` is retried.
+pub trait RetryPolicy<E>{
+    /// Called after `next` failed with `error` for the `attempt`th time in a row, starting at `1`.
+    ///
+    /// Returns the delay to wait before the next attempt, or `None` to give up and return the
+    /// error.
+    fn retry(&mut self, error: &E, attempt: u32) -> Option<Duration>;
+
+    /// Only retries errors for which `predicate` returns `true`. Other errors are returned right
+    /// away.
+    fn retry_if<F>(self, predicate: F) -> RetryIf<Self, F> where
+        Self: Sized, F: FnMut(&E) -> bool
+    {
+        RetryIf{policy: self, predicate}
+    }
+}
+
+/// Waits for the delays chosen by a `RetryPolicy`.
+///
+/// Implemented for any `FnMut(Duration)`, so a closure may be used to record the delays instead
+/// of sleeping.
+pub trait Sleeper{
+    /// Blocks for `duration`.
+    fn sleep(&mut self, duration: Duration);
+}
+
+/// Sleeps by blocking the current thread.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ThreadSleeper;
+
+impl Sleeper for ThreadSleeper{
+    fn sleep(&mut self, duration: Duration) {
+        thread::sleep(duration)
+    }
+}
+
+impl<F> Sleeper for F where F: FnMut(Duration){
+    fn sleep(&mut self, duration: Duration) {
+        self(duration)
+    }
+}
+
+/// Retries up to `retries` times in a row, always waiting for the same `delay`.
+#[derive(Clone, Copy, Debug)]
+pub struct Fixed{
+    retries: u32,
+    delay: Duration,
+}
+
+impl Fixed{
+    /// Creates a policy retrying up to `retries` times in a row, waiting `delay` before each
+    /// attempt.
+    pub fn new(retries: u32, delay: Duration) -> Self {
+        Fixed{retries, delay}
+    }
+}
+
+impl<E> RetryPolicy<E> for Fixed{
+    fn retry(&mut self, _error: &E, attempt: u32) -> Option<Duration> {
+        if attempt <= self.retries { Some(self.delay) } else { None }
+    }
+}
+
+/// Retries with exponentially growing delays, optionally reduced by a random jitter.
+///
+/// The delay before the `n`th retry is `initial * factor^(n - 1)`, but at most `max_delay`. With
+/// a jitter of `j` the delay is then multiplied with a random value in `(1 - j, 1]`, so clients
+/// failing at the same time do not retry in lockstep.
+#[derive(Clone, Debug)]
+pub struct ExponentialBackoff{
+    initial: Duration,
+    factor: f64,
+    max_delay: Duration,
+    retries: u32,
+    jitter: f64,
+    // State of the xorshift generator used for the jitter. Never zero.
+    rng: u64,
+}
+
+impl ExponentialBackoff{
+    /// Creates a policy retrying up to `retries` times in a row, starting with a delay of
+    /// `initial`, which is doubled for each attempt. There is no jitter by default.
+    pub fn new(initial: Duration, retries: u32) -> Self {
+        let seed = RandomState::new().build_hasher().finish();
+        ExponentialBackoff{
+            initial,
+            factor: 2.0,
+            max_delay: Duration::from_secs(u64::MAX),
+            retries,
+            jitter: 0.0,
+            rng: 0,
+        }.seed(seed)
+    }
+
+    /// Sets the factor by which the delay grows with each attempt.
+    pub fn factor(mut self, factor: f64) -> Self {
+        self.factor = factor;
+        self
+    }
+
+    /// Sets the upper bound of the delay.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets the fraction of the delay which may be randomly removed. Clamped to `[0, 1]`.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Seeds the random generator used for the jitter. By default it is seeded randomly. Set a
+    /// seed to obtain reproducible delays.
+    pub fn seed(mut self, seed: u64) -> Self {
+        // xorshift must not be seeded with zero
+        self.rng = if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed };
+        self
+    }
+
+    /// Returns a pseudo random number in `[0, 1)`.
+    fn random(&mut self) -> f64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl<E> RetryPolicy<E> for ExponentialBackoff{
+    fn retry(&mut self, _error: &E, attempt: u32) -> Option<Duration> {
+        if attempt > self.retries {
+            return None;
+        }
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let delay = self.initial.as_secs_f64() * self.factor.powi(exponent);
+        let delay = delay.min(self.max_delay.as_secs_f64());
+        let delay = delay * (1.0 - self.jitter * self.random());
+        Some(Duration::try_from_secs_f64(delay).unwrap_or(self.max_delay))
+    }
+}
+
+/// Retries only errors matching a predicate, according to another policy.
+///
+/// This `struct` is created by the `retry_if()` method on `RetryPolicy`
+#[derive(Clone)]
+pub struct RetryIf<P, F>{
+    policy: P,
+    predicate: F,
+}
+
+impl<E, P, F> RetryPolicy<E> for RetryIf<P, F> where
+    P: RetryPolicy<E>,
+    F: FnMut(&E) -> bool
+{
+    fn retry(&mut self, error: &E, attempt: u32) -> Option<Duration> {
+        if (self.predicate)(error) {
+            self.policy.retry(error, attempt)
+        } else {
+            None
+        }
+    }
+}
+
+/// An iterator which calls `next` of `iter` again after it failed, according to a policy.
+///
+/// This `struct` is created by the `retry()` and `retry_with_sleeper()` methods on `Iterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Retry<I, P, S = ThreadSleeper>{
+    iter: I,
+    policy: P,
+    sleeper: S,
+}
+
+impl<I, P, S> Retry<I, P, S>{
+    pub(crate) fn new(iter: I, policy: P, sleeper: S) -> Self {
+        Retry{iter, policy, sleeper}
+    }
+}
+
+impl<I, P, S> Iterrator for Retry<I, P, S> where
+    I: Iterrator,
+    P: RetryPolicy<I::Error>,
+    S: Sleeper
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<I::Item>, I::Error> {
+        let mut attempt = 0;
+        // The error being retried
+        let mut failed = None;
+        loop {
+            match self.iter.next() {
+                Err(e) => {
+                    attempt += 1;
+                    match self.policy.retry(&e, attempt) {
+                        Some(delay) => {
+                            failed = Some(e);
+                            self.sleeper.sleep(delay)
+                        }
+                        None => return Err(e),
+                    }
+                }
+                // A source ending after an error, e.g. a fused one, does not turn the error into
+                // the end of iteration
+                Ok(None) => return failed.map_or(Ok(None), Err),
+                next => return next,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Retried errors are never returned, so any of the remaining elements may vanish
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I, P, S> FusedIterrator for Retry<I, P, S> where
+    I: FusedIterrator,
+    P: RetryPolicy<I::Error>,
+    S: Sleeper {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use {convert, from_iter};
+    use test_support::Scripted;
+
+    #[test]
+    fn fixed() {
+
+        let mut delays = Vec::new();
+        let policy = Fixed::new(2, Duration::from_millis(10));
+        let source = Scripted::new(vec![
+            Err("timeout"), Err("unavailable"), Ok(Some(2)), Ok(Some(1)), Err("timeout"), Ok(Some(0)),
+        ]);
+        let result = source
+            .retry_with_sleeper(policy, |d| delays.push(d))
+            .collect::<Vec<_>>();
+        assert_eq!(result, Ok(vec![2, 1, 0]));
+        assert_eq!(delays, vec![Duration::from_millis(10); 3]);
+    }
+
+    #[test]
+    fn size_hint() {
+
+        let it = from_iter::<(), _>(0..3).retry(Fixed::new(1, Duration::from_millis(1)));
+        assert_eq!(it.size_hint(), (0, Some(3)));
+    }
+
+    #[test]
+    fn give_up() {
+
+        let mut delays = Vec::new();
+        let policy = Fixed::new(2, Duration::from_millis(10));
+        let source = Scripted::new(vec![
+            Err("timeout"), Err("unavailable"), Err("unavailable"), Ok(Some(1)), Ok(Some(0)),
+        ]);
+        let mut it = source.retry_with_sleeper(policy, |d| delays.push(d));
+        assert_eq!(it.next(), Err("unavailable"));
+        // The attempts are counted per element
+        assert_eq!(it.next(), Ok(Some(1)));
+        drop(it);
+        assert_eq!(delays.len(), 2);
+    }
+
+    #[test]
+    fn fused_source() {
+
+        let mut delays = Vec::new();
+        let source = Scripted::new(vec![Err("fatal"), Ok(Some(1))]).fuse();
+        let mut it = source.retry_with_sleeper(Fixed::new(3, Duration::from_millis(1)), |d| delays.push(d));
+        // The fused source ends after the error, which must not be mistaken for the end
+        assert_eq!(it.next(), Err("fatal"));
+        assert_eq!(it.next(), Ok(None));
+        drop(it);
+        assert_eq!(delays.len(), 1);
+    }
+
+    #[test]
+    fn resumable_source() {
+
+        // A resumable source moves on after the error, so retrying skips the failed element
+        let source = convert(vec![Ok(0), Err("bad"), Ok(1)]);
+        let it = source.retry_with_sleeper(Fixed::new(3, Duration::from_millis(1)), |_| ());
+        assert_eq!(it.collect::<Vec<_>>(), Ok(vec![0, 1]));
+    }
+
+    #[test]
+    fn exponential_backoff() {
+
+        let mut policy = ExponentialBackoff::new(Duration::from_millis(100), 4)
+            .max_delay(Duration::from_millis(500));
+        let delays: Vec<_> = (1..6).map(|attempt| RetryPolicy::<()>::retry(&mut policy, &(), attempt)).collect();
+        assert_eq!(delays, vec![
+            Some(Duration::from_millis(100)),
+            Some(Duration::from_millis(200)),
+            Some(Duration::from_millis(400)),
+            Some(Duration::from_millis(500)),
+            None,
+        ]);
+    }
+
+    #[test]
+    fn exponential_backoff_jitter() {
+
+        let policy = || ExponentialBackoff::new(Duration::from_millis(100), 10).jitter(0.5).seed(42);
+        let delays = |mut policy: ExponentialBackoff| -> Vec<_> {
+            (1..11).map(|attempt| RetryPolicy::<()>::retry(&mut policy, &(), attempt).unwrap()).collect()
+        };
+        let first = delays(policy());
+        // Same seed, same delays
+        assert_eq!(first, delays(policy()));
+        for (attempt, delay) in first.into_iter().enumerate() {
+            let max = Duration::from_millis(100) * 2u32.pow(attempt as u32);
+            assert!(delay <= max && delay > max / 2);
+        }
+    }
+
+    #[test]
+    fn retry_if() {
+
+        let mut delays = Vec::new();
+        let policy = Fixed::new(5, Duration::from_millis(1)).retry_if(|e: &&str| *e == "timeout");
+        let source = Scripted::new(vec![
+            Err("timeout"), Ok(Some(1)), Err("timeout"), Err("unavailable"), Ok(Some(0)),
+        ]);
+        let mut it = source.retry_with_sleeper(policy, |d| delays.push(d));
+        assert_eq!(it.next(), Ok(Some(1)));
+        // The second failure in a row is not a timeout and not retried
+        assert_eq!(it.next(), Err("unavailable"));
+        drop(it);
+        assert_eq!(delays.len(), 2);
+    }
+}
@@ -0,0 +1,308 @@
+//! Fallible iterators whose items borrow from the iterator itself.
+//!
+//! An `Iterrator` can not yield items referring to its own state, e.g. slices into an internal
+//! buffer which is reused for every record. A `StreamingIterrator` can: `advance` moves to the
+//! next item and `get` borrows it, so an item must be dropped before the iterator is advanced
+//! again.
+//!
+//! The type of the items borrowed for `'a` is declared by implementing `Lend<'a>`, rather than by
+//! a generic associated type `type Item<'a> where Self: 'a`. Closures taking such an item are
+//! bound for every lifetime `'a`, and for a generic associated type the compiler currently
+//! requires the iterator to be `'static` to prove these bounds. The `&'a Self` parameter of `Lend`
+//! restricts `'a` to the lifetimes the iterator outlives, so readers borrowing their input work
+//! with all adaptors and consumers.
+
+use super::Iterrator;
+
+/// Declares the type of the items a `StreamingIterrator` lends for the lifetime `'a`.
+///
+/// The second parameter must be left at its default. It limits `'a` to lifetimes `Self` outlives.
+pub trait Lend<'a, _Bound = &'a Self>{
+    /// The type of the items lent for `'a`.
+    type Item;
+}
+
+/// The type of the items `I` lends for `'a`.
+pub type Item<'a, I> = <I as Lend<'a>>::Item;
+
+/// A fallible iterator lending items which borrow from the iterator.
+pub trait StreamingIterrator: for<'a> Lend<'a>{
+    /// The type of the errors which may occur while advancing.
+    type Error;
+
+    /// Advances the iterator to the next item.
+    ///
+    /// Once the iterator is exhausted `get` returns `None`.
+    fn advance(&mut self) -> Result<(), Self::Error>;
+
+    /// Returns the current item, or `None` if the iterator has not been advanced yet or is
+    /// exhausted.
+    fn get(&self) -> Option<Item<'_, Self>>;
+
+    /// Advances the iterator and returns the next item.
+    fn next(&mut self) -> Result<Option<Item<'_, Self>>, Self::Error> {
+        self.advance()?;
+        Ok(self.get())
+    }
+
+    /// Takes a closure and creates an iterator which calls that closure on each item.
+    ///
+    /// In contrast to `Iterrator::map` the closure receives a reference to the item and may
+    /// return a reference into it, e.g. a field of a record or a part of a slice.
+    fn map_ref<T, B, F>(self, f: F) -> MapRef<Self, F> where
+        Self: Sized + for<'a> Lend<'a, Item = &'a T>,
+        T: ?Sized, B: ?Sized, F: Fn(&T) -> &B
+    {
+        MapRef{iter: self, f}
+    }
+
+    /// Creates an iterator which uses a closure to determine if an item should be lent.
+    fn filter<F>(self, predicate: F) -> Filter<Self, F> where
+        Self: Sized, F: for<'a> FnMut(&Item<'a, Self>) -> bool
+    {
+        Filter{iter: self, predicate}
+    }
+
+    /// Converts the borrowed items into owned values, yielding a regular `Iterrator`.
+    fn owned<O>(self) -> Owned<Self> where
+        Self: Sized, for<'a> Item<'a, Self>: IntoOwned<Owned = O>
+    {
+        Owned{iter: self}
+    }
+
+    /// Applies a function on all items of the iterator, producing a single, final value.
+    ///
+    /// Returns the first error encountered.
+    fn fold<B, F>(mut self, init: B, mut f: F) -> Result<B, Self::Error> where
+        Self: Sized, F: for<'a> FnMut(B, Item<'a, Self>) -> B
+    {
+        let mut accum = init;
+        while let Some(x) = self.next()?{
+            accum = f(accum, x);
+        }
+        Ok(accum)
+    }
+
+    /// Calls a closure on each item of the iterator.
+    ///
+    /// Returns the first error encountered.
+    fn for_each<F>(self, mut f: F) -> Result<(), Self::Error> where
+        Self: Sized, F: for<'a> FnMut(Item<'a, Self>)
+    {
+        self.fold((), move |(), x| f(x))
+    }
+}
+
+/// Calls a function on an argument. Implemented for every `Fn(A) -> O`.
+///
+/// Names the result of a closure whose return type depends on the lifetime of its argument.
+pub trait ItemFn<A>{
+    /// The result of the call.
+    type Output;
+
+    /// Calls the function.
+    fn call(&self, arg: A) -> Self::Output;
+}
+
+impl<A, O, F> ItemFn<A> for F where F: Fn(A) -> O{
+    type Output = O;
+
+    fn call(&self, arg: A) -> O {
+        self(arg)
+    }
+}
+
+/// Converts a borrowed item into an owned value.
+pub trait IntoOwned{
+    /// The owned type.
+    type Owned;
+
+    /// Creates an owned value, usually by cloning.
+    fn into_owned(self) -> Self::Owned;
+}
+
+impl<T> IntoOwned for &T where T: ToOwned + ?Sized{
+    type Owned = T::Owned;
+
+    fn into_owned(self) -> T::Owned {
+        self.to_owned()
+    }
+}
+
+/// A streaming iterator that maps the items of `iter` with `f`.
+///
+/// This `struct` is created by the `map_ref()` method on `StreamingIterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct MapRef<I, F>{
+    iter: I,
+    f: F,
+}
+
+impl<'a, I, F> Lend<'a> for MapRef<I, F> where I: StreamingIterrator, F: ItemFn<Item<'a, I>>{
+    type Item = F::Output;
+}
+
+impl<I, F> StreamingIterrator for MapRef<I, F> where
+    I: StreamingIterrator,
+    F: for<'a> ItemFn<Item<'a, I>>
+{
+    type Error = I::Error;
+
+    fn advance(&mut self) -> Result<(), I::Error> {
+        self.iter.advance()
+    }
+
+    fn get(&self) -> Option<Item<'_, Self>> {
+        self.iter.get().map(|x| self.f.call(x))
+    }
+}
+
+/// A streaming iterator that filters the items of `iter` with `predicate`.
+///
+/// This `struct` is created by the `filter()` method on `StreamingIterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Filter<I, F>{
+    iter: I,
+    predicate: F,
+}
+
+impl<'a, I, F> Lend<'a> for Filter<I, F> where I: StreamingIterrator{
+    type Item = Item<'a, I>;
+}
+
+impl<I, F> StreamingIterrator for Filter<I, F> where
+    I: StreamingIterrator,
+    F: for<'a> FnMut(&Item<'a, I>) -> bool
+{
+    type Error = I::Error;
+
+    fn advance(&mut self) -> Result<(), I::Error> {
+        loop {
+            self.iter.advance()?;
+            match self.iter.get() {
+                Some(ref x) if !(self.predicate)(x) => (),
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn get(&self) -> Option<Item<'_, I>> {
+        self.iter.get()
+    }
+}
+
+/// An iterator yielding owned copies of the items of a streaming iterator.
+///
+/// This `struct` is created by the `owned()` method on `StreamingIterrator`
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Owned<I>{
+    iter: I,
+}
+
+impl<I, O> Iterrator for Owned<I> where
+    I: StreamingIterrator,
+    for<'a> Item<'a, I>: IntoOwned<Owned = O>
+{
+    type Item = O;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<O>, I::Error> {
+        Ok(self.iter.next()?.map(IntoOwned::into_owned))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::io::{self, Read};
+
+    /// Reads records prefixed with their length in bytes into a reused buffer
+    struct Records<R>{
+        reader: R,
+        buffer: Vec<u8>,
+        // Whether `buffer` holds a record
+        current: bool,
+    }
+
+    impl<'a, R> Lend<'a> for Records<R>{
+        type Item = &'a [u8];
+    }
+
+    impl<R> StreamingIterrator for Records<R> where R: Read{
+        type Error = io::Error;
+
+        fn advance(&mut self) -> io::Result<()> {
+            self.current = false;
+            let mut len = [0];
+            if self.reader.read(&mut len)? == 0 {
+                return Ok(());
+            }
+            self.buffer.resize(len[0] as usize, 0);
+            self.reader.read_exact(&mut self.buffer)?;
+            self.current = true;
+            Ok(())
+        }
+
+        fn get(&self) -> Option<&[u8]> {
+            if self.current { Some(&self.buffer) } else { None }
+        }
+    }
+
+    /// Reads from borrowed input
+    fn records(bytes: &[u8]) -> Records<&[u8]> {
+        Records{reader: bytes, buffer: Vec::new(), current: false}
+    }
+
+    #[test]
+    fn next() {
+
+        let mut it = records(b"\x02ab\x00\x03cde");
+        assert_eq!(it.get(), None);
+        assert_eq!(it.next().unwrap(), Some(&b"ab"[..]));
+        assert_eq!(it.next().unwrap(), Some(&b""[..]));
+        assert_eq!(it.get(), Some(&b""[..]));
+        assert_eq!(it.next().unwrap(), Some(&b"cde"[..]));
+        assert_eq!(it.next().unwrap(), None);
+        // Truncated record
+        assert!(records(b"\x02a").next().is_err());
+    }
+
+    #[test]
+    fn map_ref() {
+
+        let it = records(b"\x02ab\x03cde").map_ref(|r| &r[1..]);
+        assert_eq!(it.owned().collect::<Vec<_>>().unwrap(), vec![b"b".to_vec(), b"de".to_vec()]);
+    }
+
+    #[test]
+    fn filter() {
+
+        let it = records(b"\x02ab\x00\x03cde").filter(|r| !r.is_empty());
+        assert_eq!(it.fold(Vec::new(), |mut v, r| { v.push(r.len()); v }).unwrap(), vec![2, 3]);
+    }
+
+    #[test]
+    fn for_each() {
+
+        let mut total = 0;
+        records(b"\x02ab\x03cde").for_each(|r| total += r.len()).unwrap();
+        assert_eq!(total, 5);
+        assert!(records(b"\x05ab").for_each(|_| ()).is_err());
+    }
+
+    #[test]
+    fn borrowed_reader() {
+
+        // A reader borrowing both its input and a reader owned by the caller
+        let input = [2, b'a', b'b', 1, b'c'];
+        let mut reader = io::Cursor::new(&input[..]);
+        let it = Records{reader: &mut reader, buffer: Vec::new(), current: false};
+        let records = it.filter(|r| r.len() > 1).owned().collect::<Vec<_>>().unwrap();
+        assert_eq!(records, vec![b"ab".to_vec()]);
+        assert_eq!(reader.position(), 5);
+    }
+}
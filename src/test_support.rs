@@ -0,0 +1,42 @@
+//! Iterators shared by the tests of all modules.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use super::Iterrator;
+
+/// Returns the outcomes it has been created with, then `Ok(None)`
+///
+/// Counts the calls to `next`. The counter can still be read after the iterator has been moved,
+/// e.g. to another thread.
+pub(crate) struct Scripted{
+    outcomes: VecDeque<Result<Option<usize>, &'static str>>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl Scripted{
+    pub(crate) fn new(outcomes: Vec<Result<Option<usize>, &'static str>>) -> Self {
+        Scripted{outcomes: outcomes.into(), calls: Arc::new(AtomicUsize::new(0))}
+    }
+
+    /// Yields `0..n`
+    pub(crate) fn numbers(n: usize) -> Self {
+        Scripted::new((0..n).map(|n| Ok(Some(n))).collect())
+    }
+
+    /// Returns the counter of the calls to `next`.
+    pub(crate) fn calls(&self) -> Arc<AtomicUsize> {
+        self.calls.clone()
+    }
+}
+
+impl Iterrator for Scripted{
+    type Item = usize;
+    type Error = &'static str;
+
+    fn next(&mut self) -> Result<Option<Self::Item>, Self::Error>{
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        self.outcomes.pop_front().unwrap_or(Ok(None))
+    }
+}